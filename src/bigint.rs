@@ -0,0 +1,572 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::ops::{Add, Div, Mul, Neg, Rem, Shl, Shr, Sub};
+use std::str::FromStr;
+use std::{fmt, fmt::Debug, fmt::Display};
+
+use crate::identity::{One, Zero};
+use crate::integers::{Integer, GCD};
+
+/// Fixed-width multiprecision signed integer of `L` 64-bit limbs.
+///
+/// Stored as a sign and a little-endian magnitude (rather than in two's complement),
+/// which keeps division simple and lets `ZERO` and `ONE` be constants.
+/// Arithmetic panics when a result does not fit in $64L$ bits,
+/// except [`Integer::mul_mod`] whose intermediate product is double width.
+///
+/// # Examples
+///
+/// ```
+/// use quadratic_sieve::bigint::I256;
+///
+/// let a: I256 = "340282366920938463463374607431768211457".parse().unwrap();
+/// let b = I256::from(3);
+/// assert_eq!((a * b).to_string(), "1020847100762815390390123822295304634371");
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt<const L: usize> {
+    neg: bool,
+    mag: [u64; L],
+}
+
+pub type I256 = BigInt<4>;
+pub type I512 = BigInt<8>;
+
+// Magnitude helpers, over little-endian limb slices
+
+fn trim(a: &[u64]) -> &[u64] {
+    let n = a.iter().rposition(|&x| x != 0).map_or(0, |i| i + 1);
+    &a[..n]
+}
+
+fn cmp_mag(a: &[u64], b: &[u64]) -> Ordering {
+    let (a, b) = (trim(a), trim(b));
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+/// In place `a += b`, returning the carry out.
+fn add_mag(a: &mut [u64], b: &[u64]) -> u64 {
+    let mut carry = 0;
+    for (i, x) in a.iter_mut().enumerate() {
+        let s = *x as u128 + b.get(i).copied().unwrap_or(0) as u128 + carry;
+        *x = s as u64;
+        carry = s >> 64;
+    }
+    carry as u64
+}
+
+/// In place `a -= b`, where `a >= b`.
+fn sub_mag(a: &mut [u64], b: &[u64]) {
+    let mut borrow = 0;
+    for (i, x) in a.iter_mut().enumerate() {
+        let d = *x as i128 - b.get(i).copied().unwrap_or(0) as i128 - borrow;
+        *x = d as u64;
+        borrow = (d < 0) as i128;
+    }
+    debug_assert_eq!(borrow, 0);
+}
+
+fn mul_mag(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (a, b) = (trim(a), trim(b));
+    let mut res = vec![0; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &y) in b.iter().enumerate() {
+            let t = x as u128 * y as u128 + res[i + j] as u128 + carry;
+            res[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        res[i + b.len()] = carry as u64;
+    }
+    res
+}
+
+fn divrem_small(a: &[u64], d: u64) -> (Vec<u64>, u64) {
+    let mut q = vec![0; a.len()];
+    let mut r = 0u128;
+    for (i, &x) in a.iter().enumerate().rev() {
+        let t = (r << 64) | x as u128;
+        q[i] = (t / d as u128) as u64;
+        r = t % d as u128;
+    }
+    (q, r as u64)
+}
+
+fn shl_mag(a: &[u64], s: u32) -> Vec<u64> {
+    let mut res = vec![0; a.len() + 1];
+    for (i, &x) in a.iter().enumerate() {
+        res[i] |= x << s;
+        if s > 0 {
+            res[i + 1] = x >> (64 - s);
+        }
+    }
+    res
+}
+
+fn shr_mag(a: &[u64], s: u32) -> Vec<u64> {
+    let mut res = vec![0; a.len()];
+    for (i, &x) in a.iter().enumerate() {
+        res[i] = x >> s;
+        if s > 0 {
+            if let Some(&y) = a.get(i + 1) {
+                res[i] |= y << (64 - s);
+            }
+        }
+    }
+    res
+}
+
+/// Quotient and remainder of magnitudes.
+///
+/// Algorithm is Knuth's algorithm D (TAOCP vol. 2, 4.3.1).
+fn divrem_mag(u: &[u64], v: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    let (u, v) = (trim(u), trim(v));
+    assert!(!v.is_empty(), "attempt to divide by zero");
+    if cmp_mag(u, v) == Ordering::Less {
+        return (Vec::new(), u.to_vec());
+    }
+    if v.len() == 1 {
+        let (q, r) = divrem_small(u, v[0]);
+        return (q, vec![r]);
+    }
+    // normalize so that the divisor's leading limb has its high bit set
+    let s = v[v.len() - 1].leading_zeros();
+    let vn = shl_mag(v, s);
+    let mut un = shl_mag(u, s);
+    let n = v.len();
+    let m = u.len() - n;
+    let b = 1u128 << 64;
+    let mut q = vec![0; m + 1];
+    for j in (0..=m).rev() {
+        let num = ((un[j + n] as u128) << 64) | un[j + n - 1] as u128;
+        let mut qhat = num / vn[n - 1] as u128;
+        let mut rhat = num % vn[n - 1] as u128;
+        while qhat >= b || qhat * vn[n - 2] as u128 > ((rhat << 64) | un[j + n - 2] as u128) {
+            qhat -= 1;
+            rhat += vn[n - 1] as u128;
+            if rhat >= b {
+                break;
+            }
+        }
+        // multiply and subtract
+        let mut borrow = 0i128;
+        let mut carry = 0u128;
+        for i in 0..n {
+            let p = qhat * vn[i] as u128 + carry;
+            carry = p >> 64;
+            let t = un[i + j] as i128 - borrow - (p as u64) as i128;
+            un[i + j] = t as u64;
+            borrow = (t < 0) as i128;
+        }
+        let t = un[j + n] as i128 - borrow - carry as i128;
+        un[j + n] = t as u64;
+        if t < 0 {
+            // subtracted one too many, so add back
+            qhat -= 1;
+            let mut carry = 0u128;
+            for i in 0..n {
+                let s = un[i + j] as u128 + vn[i] as u128 + carry;
+                un[i + j] = s as u64;
+                carry = s >> 64;
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as u64);
+        }
+        q[j] = qhat as u64;
+    }
+    (q, shr_mag(&un[..n], s))
+}
+
+impl<const L: usize> BigInt<L> {
+    /// Construct from a sign and magnitude, panicking if the magnitude overflows.
+    fn from_mag(neg: bool, mag: &[u64]) -> Self {
+        let mag = trim(mag);
+        assert!(mag.len() <= L, "BigInt<{}> overflow", L);
+        let mut res = Self::ZERO;
+        res.mag[..mag.len()].copy_from_slice(mag);
+        res.neg = neg && !mag.is_empty();
+        res
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mag.iter().all(|&x| x == 0)
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.neg
+    }
+
+    pub fn is_even(&self) -> bool {
+        self.mag[0] & 1 == 0
+    }
+
+    /// The i'th bit of the magnitude.
+    pub fn bit(&self, i: usize) -> bool {
+        i < 64 * L && (self.mag[i / 64] >> (i % 64)) & 1 == 1
+    }
+
+    /// Number of bits in the magnitude.
+    pub fn bits(&self) -> usize {
+        let mag = trim(&self.mag);
+        mag.last()
+            .map_or(0, |x| 64 * mag.len() - x.leading_zeros() as usize)
+    }
+
+    /// Number of trailing zero bits of the magnitude (zero for zero).
+    pub fn trailing_zeros(&self) -> u32 {
+        let i = match self.mag.iter().position(|&x| x != 0) {
+            Some(i) => i,
+            None => return 0,
+        };
+        64 * i as u32 + self.mag[i].trailing_zeros()
+    }
+
+    /// Quotient and remainder, truncated towards zero (like the primitive integers).
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let (q, r) = divrem_mag(&self.mag, &rhs.mag);
+        (
+            Self::from_mag(self.neg != rhs.neg, &q),
+            Self::from_mag(self.neg, &r),
+        )
+    }
+
+    /// Least non-negative remainder modulo `m`.
+    pub fn rem_euclid(&self, m: &Self) -> Self {
+        let r = self % m;
+        if r.neg {
+            r + m.abs()
+        } else {
+            r
+        }
+    }
+}
+
+impl<const L: usize> Zero for BigInt<L> {
+    type Element = BigInt<L>;
+
+    const ZERO: BigInt<L> = BigInt {
+        neg: false,
+        mag: [0; L],
+    };
+}
+
+impl<const L: usize> One for BigInt<L> {
+    type Element = BigInt<L>;
+
+    const ONE: BigInt<L> = {
+        let mut mag = [0; L];
+        mag[0] = 1;
+        BigInt { neg: false, mag }
+    };
+}
+
+impl<const L: usize> Ord for BigInt<L> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.neg, other.neg) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_mag(&self.mag, &other.mag),
+            (true, true) => cmp_mag(&other.mag, &self.mag),
+        }
+    }
+}
+
+impl<const L: usize> PartialOrd for BigInt<L> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! impl_from_signed {
+    ($($T:ty),*) => {
+        $(
+            impl<const L: usize> From<$T> for BigInt<L> {
+                fn from(a: $T) -> Self {
+                    Self::from_mag(a < 0, &[(a as i128).unsigned_abs() as u64])
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_unsigned {
+    ($($T:ty),*) => {
+        $(
+            impl<const L: usize> From<$T> for BigInt<L> {
+                fn from(a: $T) -> Self {
+                    Self::from_mag(false, &[a as u64])
+                }
+            }
+        )*
+    };
+}
+
+impl_from_signed!(i8, i16, i32, i64);
+impl_from_unsigned!(u8, u16, u32, u64);
+
+impl<const L: usize> TryFrom<BigInt<L>> for i64 {
+    type Error = String;
+
+    fn try_from(a: BigInt<L>) -> Result<i64, String> {
+        let mag = trim(&a.mag);
+        match mag {
+            [] => Ok(0),
+            [x] if a.neg && *x <= 1 << 63 => Ok((*x as i64).wrapping_neg()),
+            [x] if !a.neg && *x < 1 << 63 => Ok(*x as i64),
+            _ => Err(format!("{} does not fit in i64", a)),
+        }
+    }
+}
+
+impl<const L: usize> TryFrom<BigInt<L>> for u64 {
+    type Error = String;
+
+    fn try_from(a: BigInt<L>) -> Result<u64, String> {
+        match trim(&a.mag) {
+            [] => Ok(0),
+            [x] if !a.neg => Ok(*x),
+            _ => Err(format!("{} does not fit in u64", a)),
+        }
+    }
+}
+
+impl<const L: usize> Neg for BigInt<L> {
+    type Output = BigInt<L>;
+
+    fn neg(mut self) -> BigInt<L> {
+        self.neg = !self.neg && !self.is_zero();
+        self
+    }
+}
+
+impl<const L: usize> Neg for &BigInt<L> {
+    type Output = BigInt<L>;
+
+    fn neg(self) -> BigInt<L> {
+        -*self
+    }
+}
+
+fn add<const L: usize>(a: &BigInt<L>, b: &BigInt<L>, b_neg: bool) -> BigInt<L> {
+    if a.neg == b_neg {
+        let mut mag = a.mag;
+        let carry = add_mag(&mut mag, &b.mag);
+        assert!(carry == 0, "BigInt<{}> overflow", L);
+        BigInt::from_mag(a.neg, &mag)
+    } else if cmp_mag(&a.mag, &b.mag) != Ordering::Less {
+        let mut mag = a.mag;
+        sub_mag(&mut mag, &b.mag);
+        BigInt::from_mag(a.neg, &mag)
+    } else {
+        let mut mag = b.mag;
+        sub_mag(&mut mag, &a.mag);
+        BigInt::from_mag(b_neg, &mag)
+    }
+}
+
+// Helper for implementing operations on reference variants
+macro_rules! impl_op {
+    (@variant $TL:ty, $TR:ty, $Op:ident, $op:ident, $f:expr) => {
+        impl<'a, 'b, const L: usize> $Op<$TR> for $TL {
+            type Output = BigInt<L>;
+
+            fn $op(self, rhs: $TR) -> BigInt<L> {
+                let f: fn(&BigInt<L>, &BigInt<L>) -> BigInt<L> = $f;
+                f(&self, &rhs)
+            }
+        }
+    };
+    ($Op:ident, $op:ident, $f:expr) => {
+        impl_op!(@variant     BigInt<L>,     BigInt<L>, $Op, $op, $f);
+        impl_op!(@variant &'a BigInt<L>,     BigInt<L>, $Op, $op, $f);
+        impl_op!(@variant     BigInt<L>, &'b BigInt<L>, $Op, $op, $f);
+        impl_op!(@variant &'a BigInt<L>, &'b BigInt<L>, $Op, $op, $f);
+    };
+}
+
+impl_op!(Add, add, |a, b| add(a, b, b.neg));
+impl_op!(Sub, sub, |a, b| add(a, b, !b.neg && !b.is_zero()));
+impl_op!(Mul, mul, |a, b| BigInt::from_mag(
+    a.neg != b.neg,
+    &mul_mag(&a.mag, &b.mag)
+));
+impl_op!(Div, div, |a, b| a.div_rem(b).0);
+impl_op!(Rem, rem, |a, b| a.div_rem(b).1);
+
+impl<const L: usize> Shl<u32> for BigInt<L> {
+    type Output = BigInt<L>;
+
+    /// Shift the magnitude left (sign is kept).
+    fn shl(self, s: u32) -> BigInt<L> {
+        let (w, s) = ((s / 64) as usize, s % 64);
+        let mut mag = vec![0; w];
+        mag.extend(shl_mag(&self.mag, s));
+        BigInt::from_mag(self.neg, &mag)
+    }
+}
+
+impl<const L: usize> Shr<u32> for BigInt<L> {
+    type Output = BigInt<L>;
+
+    /// Shift the magnitude right (sign is kept, so this truncates towards zero).
+    fn shr(self, s: u32) -> BigInt<L> {
+        let (w, s) = ((s / 64) as usize, s % 64);
+        let mag = if w < L {
+            shr_mag(&self.mag[w..], s)
+        } else {
+            Vec::new()
+        };
+        BigInt::from_mag(self.neg, &mag)
+    }
+}
+
+impl<const L: usize> GCD for BigInt<L> {
+    fn gcd(self, rhs: Self) -> Self {
+        let mut m = self.abs();
+        let mut n = rhs.abs();
+        while !n.is_zero() {
+            let r = m % n;
+            m = n;
+            n = r;
+        }
+        m
+    }
+}
+
+impl<const L: usize> Integer for BigInt<L> {
+    const BITS: u32 = 64 * L as u32;
+
+    fn abs(&self) -> Self {
+        Self {
+            neg: false,
+            mag: self.mag,
+        }
+    }
+
+    fn pow(&self, mut e: u32) -> Self {
+        let mut b = *self;
+        let mut res = Self::ONE;
+        while e > 0 {
+            if e % 2 == 1 {
+                res = res * b;
+            }
+            e >>= 1;
+            if e > 0 {
+                b = b * b;
+            }
+        }
+        res
+    }
+
+    /// Floor of the base 2 logarithm of the magnitude (-1 for zero).
+    fn log2(&self) -> Self {
+        Self::from(self.bits() as u64) - Self::ONE
+    }
+
+    fn bit_length(&self) -> u32 {
+        self.bits() as u32
+    }
+
+    fn pow_mod(&self, mut e: u32, m: Self) -> Self {
+        let mut b = self.rem_euclid(&m);
+        let mut res = Self::ONE.rem_euclid(&m);
+        while e > 0 {
+            if e % 2 == 1 {
+                res = res.mul_mod(&b, &m);
+            }
+            e >>= 1;
+            b = b.mul_mod(&b, &m);
+        }
+        res
+    }
+
+    fn mul_mod(&self, rhs: &Self, m: &Self) -> Self {
+        let (_, r) = divrem_mag(&mul_mag(&self.mag, &rhs.mag), &m.mag);
+        let r = Self::from_mag(false, &r);
+        if self.neg != rhs.neg && !r.is_zero() {
+            m.abs() - r
+        } else {
+            r
+        }
+    }
+
+    fn sqrt(&self) -> Self {
+        assert!(!self.neg, "square root of negative integer");
+        if self.is_zero() {
+            return Self::ZERO;
+        }
+        // Newton's method, from above
+        let mut x = Self::ONE << (self.bits() as u32).div_ceil(2);
+        loop {
+            let y = (x + *self / x) >> 1;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn rem_small(&self, m: u32) -> u32 {
+        let (_, r) = divrem_small(&self.mag, m as u64);
+        if self.neg && r != 0 {
+            m - r as u32
+        } else {
+            r as u32
+        }
+    }
+}
+
+impl<const L: usize> FromStr for BigInt<L> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let (neg, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        if digits.is_empty() {
+            return Err(format!("cannot parse {:?} as integer", s));
+        }
+        let mut mag = [0; L];
+        for c in digits.chars() {
+            let d = c
+                .to_digit(10)
+                .ok_or_else(|| format!("cannot parse {:?} as integer", s))?;
+            let mut carry = d as u128;
+            for x in mag.iter_mut() {
+                let t = *x as u128 * 10 + carry;
+                *x = t as u64;
+                carry = t >> 64;
+            }
+            if carry != 0 {
+                return Err(format!("{:?} overflows BigInt<{}>", s, L));
+            }
+        }
+        Ok(Self::from_mag(neg, &mag))
+    }
+}
+
+impl<const L: usize> Display for BigInt<L> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut chunks = Vec::new();
+        let mut mag = trim(&self.mag).to_vec();
+        while !mag.is_empty() {
+            let (q, r) = divrem_small(&mag, CHUNK);
+            chunks.push(r);
+            mag = trim(&q).to_vec();
+        }
+        let mut digits = chunks.pop().unwrap_or(0).to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:019}", chunk));
+        }
+        fmt.pad_integral(!self.neg, "", &digits)
+    }
+}
+
+impl<const L: usize> Debug for BigInt<L> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(self, fmt)
+    }
+}
@@ -0,0 +1,243 @@
+use std::{fmt, fmt::Display};
+
+use crate::congruence_class::CongruenceClass;
+use crate::dyn_matrix::DynMatrix;
+use crate::identity::{One, Zero};
+
+const WORD_BITS: usize = 64;
+
+#[inline]
+fn words_for(n: usize) -> usize {
+    n.div_ceil(WORD_BITS)
+}
+
+/// Bit-packed matrix over $\mathbb{Z}/2\mathbb{Z}$.
+///
+/// Rows are stored as `u64` words so that row operations
+/// (which over $\mathbb{Z}/2\mathbb{Z}$ are all XOR) act on 64 entries at once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BitMatrix {
+    rows: Vec<Vec<u64>>,
+    m: usize,
+    n: usize,
+}
+
+impl BitMatrix {
+    pub fn zeroes(m: usize, n: usize) -> Self {
+        Self {
+            rows: vec![vec![0; words_for(n)]; m],
+            m,
+            n,
+        }
+    }
+
+    pub fn eye(m: usize, n: usize) -> Self {
+        let mut res = Self::zeroes(m, n);
+        for i in 0..m.min(n) {
+            res.set(i, i, true);
+        }
+        res
+    }
+
+    /// Construct from rows of bits.
+    ///
+    /// Panics if the rows are not all of the same length.
+    pub fn from_bits(rows: &[Vec<bool>]) -> Self {
+        let n = rows.first().map_or(0, Vec::len);
+        let mut res = Self::zeroes(rows.len(), n);
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(row.len(), n, "rows of unequal length");
+            for (j, &b) in row.iter().enumerate() {
+                res.set(i, j, b);
+            }
+        }
+        res
+    }
+
+    /// Number of rows.
+    pub fn num_rows(&self) -> usize {
+        self.m
+    }
+
+    /// Number of columns.
+    pub fn num_cols(&self) -> usize {
+        self.n
+    }
+
+    /// Dimensions as (rows, columns).
+    pub fn shape(&self) -> (usize, usize) {
+        (self.m, self.n)
+    }
+
+    #[inline]
+    pub fn get(&self, i: usize, j: usize) -> bool {
+        (self.rows[i][j / WORD_BITS] >> (j % WORD_BITS)) & 1 == 1
+    }
+
+    #[inline]
+    pub fn set(&mut self, i: usize, j: usize, b: bool) {
+        let mask = 1 << (j % WORD_BITS);
+        if b {
+            self.rows[i][j / WORD_BITS] |= mask;
+        } else {
+            self.rows[i][j / WORD_BITS] &= !mask;
+        }
+    }
+
+    #[inline]
+    pub fn flip(&mut self, i: usize, j: usize) {
+        self.rows[i][j / WORD_BITS] ^= 1 << (j % WORD_BITS);
+    }
+
+    /// Iterator over matrix elements of the i'th row.
+    pub fn iter_row(&self, i: usize) -> impl Iterator<Item = bool> + '_ {
+        (0..self.n).map(move |j| self.get(i, j))
+    }
+
+    /// Iterator over matrix elements of the j'th column.
+    pub fn iter_col(&self, j: usize) -> impl Iterator<Item = bool> + '_ {
+        (0..self.m).map(move |i| self.get(i, j))
+    }
+
+    /// If the i'th row is zero in all of its components.
+    pub fn is_zero_row(&self, i: usize) -> bool {
+        self.rows[i].iter().all(|&w| w == 0)
+    }
+
+    pub fn transpose(&self) -> BitMatrix {
+        let mut res = BitMatrix::zeroes(self.n, self.m);
+        for i in 0..self.m {
+            for j in (0..self.n).filter(|&j| self.get(i, j)) {
+                res.set(j, i, true);
+            }
+        }
+        res
+    }
+
+    /// Augment with the identity matrix, giving an $m\times(n+m)$ matrix.
+    pub fn augment(&self) -> BitMatrix {
+        let mut res = BitMatrix::zeroes(self.m, self.n + self.m);
+        for (i, (r, s)) in res.rows.iter_mut().zip(self.rows.iter()).enumerate() {
+            r[..s.len()].copy_from_slice(s);
+            let j = self.n + i;
+            r[j / WORD_BITS] |= 1 << (j % WORD_BITS);
+        }
+        res
+    }
+
+    /// Find index $i$ of first row with $i\ge s$
+    /// that is non-zero in its $j$'th component.
+    pub fn find_nonzero_in_col_after(&self, j: usize, s: usize) -> Option<usize> {
+        (s..self.m).find(|&i| self.get(i, j))
+    }
+
+    /// Add (XOR) the r'th row into the i'th row, from word $w$ onward.
+    #[inline]
+    fn xor_row_from(&mut self, r: usize, i: usize, w: usize) {
+        let (src, dst) = if r < i {
+            let (a, b) = self.rows.split_at_mut(i);
+            (&a[r], &mut b[0])
+        } else {
+            let (a, b) = self.rows.split_at_mut(r);
+            (&b[0], &mut a[i])
+        };
+        for (d, s) in dst[w..].iter_mut().zip(src[w..].iter()) {
+            *d ^= s;
+        }
+    }
+
+    /// Bring the matrix to echelon form with XOR row operations.
+    ///
+    /// Pivots are chosen exactly as [`DynMatrix::to_echelon`] chooses them,
+    /// so that both produce the same echelon form over $\mathbb{Z}/2\mathbb{Z}$.
+    pub fn to_echelon(&self) -> Self {
+        let mut ech = self.clone();
+        let mut r = 0;
+        for j in 0..self.n {
+            if r == self.m {
+                break;
+            }
+            if let Some(i) = ech.find_nonzero_in_col_after(j, r) {
+                ech.rows.swap(i, r);
+                // Rows at and below r are zero in all columns before j,
+                // so the words before j's can be skipped.
+                let w = j / WORD_BITS;
+                for i in (r + 1)..self.m {
+                    if ech.get(i, j) {
+                        ech.xor_row_from(r, i, w);
+                    }
+                }
+                r += 1;
+            }
+        }
+        ech
+    }
+
+    /// Iterator over spanning m length row-vectors in the left-nullspace of the matrix.
+    pub fn iter_lnull_span(&self) -> impl Iterator<Item = Vec<bool>> {
+        let (m, n) = self.shape();
+        let ech = self.augment().to_echelon();
+        let subsets: Vec<Vec<bool>> = (0..m)
+            .filter(|&i| (0..n).all(|j| !ech.get(i, j)))
+            .map(|i| (n..n + m).map(|j| ech.get(i, j)).collect())
+            .collect();
+        subsets.into_iter()
+    }
+}
+
+impl From<&DynMatrix<CongruenceClass<2>>> for BitMatrix {
+    fn from(mat: &DynMatrix<CongruenceClass<2>>) -> Self {
+        let (m, n) = mat.shape();
+        let mut res = BitMatrix::zeroes(m, n);
+        for (i, row) in mat.rows.iter().enumerate() {
+            for (j, a) in row.iter().enumerate() {
+                res.set(i, j, a != &CongruenceClass::<2>::ZERO);
+            }
+        }
+        res
+    }
+}
+
+impl From<&BitMatrix> for DynMatrix<CongruenceClass<2>> {
+    fn from(mat: &BitMatrix) -> Self {
+        let rows = (0..mat.m)
+            .map(|i| {
+                mat.iter_row(i)
+                    .map(|b| {
+                        if b {
+                            CongruenceClass::<2>::ONE
+                        } else {
+                            CongruenceClass::<2>::ZERO
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        DynMatrix::from_vec(rows)
+    }
+}
+
+impl Display for BitMatrix {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        macro_rules! write_end {
+            ($l:literal, $r:literal) => {
+                write!(fmt, $l)?;
+                for _ in 0..self.n {
+                    write!(fmt, "  ")?;
+                }
+                writeln!(fmt, $r)?;
+            };
+        }
+
+        write_end!("┌ ", "┐");
+        for i in 0..self.m {
+            write!(fmt, "│ ")?;
+            for b in self.iter_row(i) {
+                write!(fmt, "{} ", b as u8)?;
+            }
+            writeln!(fmt, "│")?;
+        }
+        write_end!("└ ", "┘");
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::integers::IntegerRing;
+
+/// Exponent of $b > 1$ in $n$, dividing it out of $n$.
+fn divide_out<T: IntegerRing>(n: &mut T, b: &T) -> u32 {
+    let mut e = 0;
+    while n.clone() % b.clone() == T::ZERO {
+        *n = n.clone() / b.clone();
+        e += 1;
+    }
+    e
+}
+
+/// Coprime base of a multiset of positive integers, as in Bernstein's "Factoring into coprimes
+/// in essentially linear time": pairwise coprime $b_1 < \ldots < b_m$, all above 1, such that
+/// every input is a product of their powers, $n_i = \prod_j b_j^{e_{ij}}$.
+///
+/// Refines the inputs one at a time: an element which shares a factor $g$ with one of the base
+/// so far, $b$, is replaced by $g$, $b / g$ and itself divided by $g$, each refined in turn
+/// (so it takes quadratic rather than essentially linear time, which is plenty for the handful
+/// of factors from a sieve or from curves). The product of everything left to refine only
+/// decreases, so this ends, and each input is a product of what it was split into.
+///
+/// Returns the base, alongside the exponent vectors $(e_{i1}, \ldots, e_{im})$ of the inputs.
+///
+/// # Panics
+///
+/// If any input is not positive.
+pub fn coprime_base<T: IntegerRing>(ns: &[T]) -> (Vec<T>, Vec<Vec<u32>>) {
+    assert!(
+        ns.iter().all(|n| n > &T::ZERO),
+        "coprime base of non-positive integers"
+    );
+    let mut base: Vec<T> = Vec::new();
+    let mut work: Vec<T> = ns.to_vec();
+    while let Some(x) = work.pop() {
+        if x == T::ONE {
+            continue;
+        }
+        let shared = base.iter().enumerate().find_map(|(i, b)| {
+            let g = b.clone().gcd(x.clone());
+            (g != T::ONE).then_some((i, g))
+        });
+        match shared {
+            Some((i, g)) => {
+                let b = base.swap_remove(i);
+                work.push(b / g.clone());
+                work.push(x / g.clone());
+                work.push(g);
+            }
+            None => base.push(x),
+        }
+    }
+    base.sort();
+    let exponents = ns
+        .iter()
+        .map(|n| {
+            let mut n = n.clone();
+            base.iter().map(|b| divide_out(&mut n, b)).collect()
+        })
+        .collect();
+    (base, exponents)
+}
@@ -0,0 +1,69 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::integers::IntegerRing;
+use crate::quadratic_sieve::{
+    check, find_factor, primes, QsError, Relations, Stats, EXTRA_RELATIONS, LARGE_PRIME_MULTIPLIER,
+};
+
+/// Uniformly random integer in $[0, n)$ (up to a negligible bias),
+/// from 16 bit chunks reduced modulo $n$ as they are taken in, so that any `T` can hold them.
+fn random_below<T: IntegerRing>(n: &T, rng: &mut StdRng) -> T {
+    let base = T::from(1 << 16);
+    let chunks = n.bit_length() / 16 + 2;
+    let mut x = T::ZERO;
+    for _ in 0..chunks {
+        x = (x.mul_mod(&base, n) + T::from(rng.gen_range(0..1 << 16))) % n.clone();
+    }
+    x
+}
+
+/// Dixon's random squares method
+///
+/// Picks random $x \in [\sqrt n, n)$ and trial divides each $y = x^2 \bmod n$ over the
+/// factor base, the first `b` primes (and -1), keeping those which are smooth (up to
+/// large primes). Relations are combined into a congruence of squares just as by
+/// [`crate::quadratic_sieve::qs`], but the $y$ are as large as $n$ rather than about
+/// $\sqrt n$, and every one of them is trial divided where the sieve picks out the
+/// likely smooth ones, so far fewer of them give relations (see [`dixon_with_stats`]).
+///
+/// * `n` - Integer to factor
+/// * `b` - Desired factor base length
+/// * `i` - Number of random $x$ to try
+///
+/// Returns a non-trivial factor pair `(d, n / d)`.
+pub fn dixon<T: IntegerRing>(n: T, b: usize, i: usize) -> Result<(T, T), QsError> {
+    dixon_with_stats(n, b, i).0
+}
+
+/// [`dixon`], alongside the [`Stats`] of its relation collection
+/// (all zero if it doesn't get that far).
+pub fn dixon_with_stats<T: IntegerRing>(
+    n: T,
+    b: usize,
+    i: usize,
+) -> (Result<(T, T), QsError>, Stats) {
+    if let Err(e) = check(&n) {
+        return (Err(e), Stats::default());
+    }
+    let fb: Vec<i32> = primes().take(b).collect();
+    let needed = fb.len() + 1 + EXTRA_RELATIONS;
+    let mut relations = Relations::new(&n, &fb, LARGE_PRIME_MULTIPLIER);
+    let mut rng = StdRng::seed_from_u64(0);
+    let r = n.sqrt() + T::ONE;
+    for _ in 0..i {
+        let x = r.clone() + random_below(&(n.clone() - r.clone()), &mut rng);
+        let y = x.mul_mod(&x, &n);
+        // x shares a factor with n, which is too lucky to count on
+        if y != T::ZERO {
+            relations.add(x, y, T::ONE);
+        }
+        if relations.len() >= needed {
+            return (find_factor(&n, &relations), relations.stats());
+        }
+    }
+    let err = QsError::NotEnoughRelations {
+        found: relations.len(),
+        needed,
+    };
+    (Err(err), relations.stats())
+}
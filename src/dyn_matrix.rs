@@ -0,0 +1,455 @@
+use std::convert::TryFrom;
+use std::ops::{Add, Div, Mul, Sub};
+use std::{fmt, fmt::Display};
+
+use array_init::array_init;
+use itertools::{iproduct, izip};
+
+use crate::conjugate::Conjugate;
+use crate::identity::{One, Zero};
+use crate::integers::LCM;
+use crate::matrix::{Matrix, Primitive, PrimitiveRef};
+
+/// Heap-backed matrix whose dimensions are only known at runtime.
+///
+/// Mirrors the API of [`Matrix`], for when the dimensions cannot be made
+/// constant (e.g. the number of relations found by the sieve).
+#[derive(Clone, Debug)]
+pub struct DynMatrix<T>
+where
+    T: Clone,
+{
+    pub rows: Vec<Vec<T>>,
+    m: usize,
+    n: usize,
+}
+
+impl<T> DynMatrix<T>
+where
+    T: Clone,
+{
+    /// Construct from a vector of rows.
+    ///
+    /// Panics if the rows are not all of the same length.
+    pub fn from_vec(rows: Vec<Vec<T>>) -> Self {
+        let m = rows.len();
+        let n = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == n),
+            "rows of unequal length"
+        );
+        Self { rows, m, n }
+    }
+
+    pub fn from_value(m: usize, n: usize, value: T) -> Self {
+        Self::from_vec(vec![vec![value; n]; m])
+    }
+
+    /// Number of rows.
+    pub fn num_rows(&self) -> usize {
+        self.m
+    }
+
+    /// Number of columns.
+    pub fn num_cols(&self) -> usize {
+        self.n
+    }
+
+    /// Dimensions as (rows, columns).
+    pub fn shape(&self) -> (usize, usize) {
+        (self.m, self.n)
+    }
+
+    /// Iterator over elements in row-major order.
+    pub fn iter_row_major(&self) -> impl Iterator<Item = &T> {
+        self.rows.iter().flat_map(|row| row.iter())
+    }
+
+    /// Mutable iterator over elements in row-major order.
+    pub fn iter_mut_row_major(&mut self) -> impl Iterator<Item = &mut T> {
+        self.rows.iter_mut().flat_map(|row| row.iter_mut())
+    }
+
+    /// Iterator over elements in column-major order.
+    pub fn iter_col_major(&self) -> impl Iterator<Item = &T> {
+        (0..self.n).flat_map(move |j| self.rows.iter().map(move |row| &row[j]))
+    }
+
+    /// Mutable iterator over matrix elements in column-major order.
+    pub fn iter_mut_col_major(&mut self) -> impl Iterator<Item = &mut T> {
+        let rows_ptr = self.rows.as_mut_ptr();
+        iproduct!(0..self.n, 0..self.m).map(move |(j, i)| {
+            let row = unsafe { rows_ptr.add(i).as_mut().unwrap() };
+            &mut row[j]
+        })
+    }
+
+    /// Iterator over matrix elements of the i'th row.
+    pub fn iter_row(&self, i: usize) -> impl Iterator<Item = &T> {
+        self.rows[i].iter()
+    }
+
+    /// Mutable iterator over matrix elements of the i'th row.
+    pub fn iter_mut_row(&mut self, i: usize) -> impl Iterator<Item = &mut T> {
+        self.rows[i].iter_mut()
+    }
+
+    /// Iterator over matrix elements of the j'th column.
+    pub fn iter_col(&self, j: usize) -> impl Iterator<Item = &T> {
+        self.rows.iter().map(move |row| &row[j])
+    }
+
+    /// Mutable iterator over matrix elements of the j'th column.
+    pub fn iter_mut_col(&mut self, j: usize) -> impl Iterator<Item = &mut T> {
+        self.rows.iter_mut().map(move |row| &mut row[j])
+    }
+
+    /// Iterator over matrix elements on the main diagonal.
+    pub fn iter_diag(&self) -> impl Iterator<Item = &T> {
+        self.rows
+            .iter()
+            .enumerate()
+            .filter_map(|(i, row)| row.get(i))
+    }
+
+    /// Mutable iterator over matrix elements on the main diagonal.
+    pub fn iter_mut_diag(&mut self) -> impl Iterator<Item = &mut T> {
+        self.rows
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, row)| row.get_mut(i))
+    }
+
+    /// Iterator over matrix elements on the i'th superdiagonal.
+    pub fn iter_supdiag(&self, j: usize) -> impl Iterator<Item = &T> {
+        self.rows
+            .iter()
+            .enumerate()
+            .filter_map(move |(i, row)| row.get(i + j))
+    }
+
+    /// Mutable iterator over matrix elements on the i'th superdiagonal.
+    pub fn iter_mut_supdiag(&mut self, j: usize) -> impl Iterator<Item = &mut T> {
+        self.rows
+            .iter_mut()
+            .enumerate()
+            .filter_map(move |(k, row)| row.get_mut(k + j))
+    }
+
+    /// Iterator over matrix elements on the i'th subdiagonal.
+    pub fn iter_subdiag(&self, i: usize) -> impl Iterator<Item = &T> {
+        self.rows
+            .iter()
+            .skip(i)
+            .enumerate()
+            .filter_map(move |(k, row)| row.get(k))
+    }
+
+    /// Mutable iterator over matrix elements on the i'th subdiagonal.
+    pub fn iter_mut_subdiag(&mut self, i: usize) -> impl Iterator<Item = &mut T> {
+        self.rows
+            .iter_mut()
+            .skip(i)
+            .enumerate()
+            .filter_map(move |(k, row)| row.get_mut(k))
+    }
+
+    pub fn transpose(&self) -> DynMatrix<T> {
+        let rows = (0..self.n)
+            .map(|j| self.iter_col(j).cloned().collect())
+            .collect();
+        DynMatrix {
+            rows,
+            m: self.n,
+            n: self.m,
+        }
+    }
+}
+
+impl<T> PartialEq for DynMatrix<T>
+where
+    T: PartialEq + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.shape() == other.shape()
+            && self
+                .iter_row_major()
+                .zip(other.iter_row_major())
+                .all(|(a, b)| a == b)
+    }
+}
+
+impl<T> DynMatrix<T>
+where
+    T: Zero<Element = T> + Clone,
+{
+    pub fn zeroes(m: usize, n: usize) -> Self {
+        Self::from_value(m, n, T::ZERO)
+    }
+}
+
+impl<T> DynMatrix<T>
+where
+    T: Zero<Element = T> + PartialEq + Clone,
+{
+    /// Find index $i$ of first row with $i\ge s$
+    /// that is non-zero in its $j$'th component.
+    pub fn find_nonzero_in_col_after(&self, j: usize, s: usize) -> Option<usize> {
+        self.rows
+            .iter()
+            .enumerate()
+            .skip(s)
+            .find_map(|(i, row)| (row[j] != T::ZERO).then_some(i))
+    }
+
+    /// Find index $i$ of first row that is non-zero in its $j$'th component.
+    pub fn find_nonzero_in_col(&self, j: usize) -> Option<usize> {
+        self.find_nonzero_in_col_after(j, 0)
+    }
+
+    /// Find index $j$ of first column with $j\ge s$
+    /// that is non-zero in its $i$'th component.
+    pub fn find_nonzero_in_row_after(&self, i: usize, s: usize) -> Option<usize> {
+        self.rows[i]
+            .iter()
+            .enumerate()
+            .skip(s)
+            .find_map(|(j, x)| (x != &T::ZERO).then_some(j))
+    }
+
+    /// Find index $j$ of first column that is non-zero in its $i$'th component.
+    pub fn find_nonzero_in_row(&self, i: usize) -> Option<usize> {
+        self.find_nonzero_in_row_after(i, 0)
+    }
+}
+
+impl<T> DynMatrix<T>
+where
+    T: Zero<Element = T> + One<Element = T> + Clone,
+{
+    pub fn eye(m: usize, n: usize) -> Self {
+        let mut res = Self::zeroes(m, n);
+        res.iter_mut_diag().for_each(|a| *a = T::ONE);
+        res
+    }
+
+    /// Augment with the identity matrix, giving an $m\times(n+m)$ matrix.
+    pub fn augment(&self) -> DynMatrix<T> {
+        let rows = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut row = row.clone();
+                row.extend((0..self.m).map(|j| if i == j { T::ONE } else { T::ZERO }));
+                row
+            })
+            .collect();
+        DynMatrix {
+            rows,
+            m: self.m,
+            n: self.n + self.m,
+        }
+    }
+}
+
+// Helper for implementing addition/subtraction on matrix reference variants
+macro_rules! impl_op {
+    (@variant $TL:ty, $TR:ty, $Op:ident, $op:tt) => {
+        impl<'a, 'b, T> $Op<$TR> for $TL
+        where
+            T: Primitive<T>,
+            for<'c, 'd> &'c T: PrimitiveRef<T, &'d T>,
+        {
+            type Output = DynMatrix<T>;
+
+            fn $op(self, rhs: $TR) -> DynMatrix<T> {
+                assert_eq!(self.shape(), rhs.shape(), "mismatched dimensions");
+                let rows = izip!(self.rows.iter(), rhs.rows.iter())
+                    .map(|(r, s)| izip!(r, s).map(|(a, b)| $Op::$op(a, b)).collect())
+                    .collect();
+                DynMatrix::from_vec(rows)
+            }
+        }
+    };
+    ($Op:ident, $op:tt) => {
+        impl_op!(@variant     DynMatrix<T>,     DynMatrix<T>, $Op, $op);
+        impl_op!(@variant &'a DynMatrix<T>,     DynMatrix<T>, $Op, $op);
+        impl_op!(@variant     DynMatrix<T>, &'b DynMatrix<T>, $Op, $op);
+        impl_op!(@variant &'a DynMatrix<T>, &'b DynMatrix<T>, $Op, $op);
+    };
+}
+
+macro_rules! impl_mul {
+    (@variant $TL:ty, $TR:ty) => {
+        impl<'a, 'b, T> Mul<$TR> for $TL
+        where
+            T: Primitive<T> + std::iter::Sum,
+            for<'c, 'd> &'c T: PrimitiveRef<T, &'d T>,
+        {
+            type Output = DynMatrix<T>;
+
+            fn mul(self, rhs: $TR) -> DynMatrix<T> {
+                assert_eq!(self.n, rhs.m, "mismatched dimensions");
+                let rows = (0..self.m)
+                    .map(|i| {
+                        (0..rhs.n)
+                            .map(|j| {
+                                self.iter_row(i)
+                                    .zip(rhs.iter_col(j))
+                                    .map(|(a, b)| a * b)
+                                    .sum()
+                            })
+                            .collect()
+                    })
+                    .collect();
+                DynMatrix {
+                    rows,
+                    m: self.m,
+                    n: rhs.n,
+                }
+            }
+        }
+    };
+    () => {
+        impl_mul!(@variant     DynMatrix<T>,     DynMatrix<T>);
+        impl_mul!(@variant &'a DynMatrix<T>,     DynMatrix<T>);
+        impl_mul!(@variant     DynMatrix<T>, &'b DynMatrix<T>);
+        impl_mul!(@variant &'a DynMatrix<T>, &'b DynMatrix<T>);
+    };
+}
+
+impl_op!(Add, add);
+impl_op!(Sub, sub);
+impl_mul!();
+
+impl<T> DynMatrix<T>
+where
+    T: Zero<Element = T> + LCM + Sub<Output = T> + PartialEq + Clone,
+    for<'b> T: Div<&'b T, Output = T>,
+    for<'a, 'b> &'a T: Mul<&'b T, Output = T> + Div<&'b T, Output = T>,
+{
+    pub fn to_echelon(&self) -> Self {
+        let mut ech = self.clone();
+        let mut r = 0;
+        for j in 0..self.n {
+            if let Some(i) = ech.find_nonzero_in_col_after(j, r) {
+                if r != i {
+                    ech.rows.swap(i, r);
+                }
+                let a = ech.rows[r][j].clone();
+                for i in (r + 1)..self.m {
+                    if ech.rows[i][j] != T::ZERO {
+                        let b = ech.rows[i][j].clone();
+                        let lcm = b.clone().lcm(a.clone());
+                        let c = &lcm / &a;
+                        let d = &lcm / &b;
+                        for k in 0..self.n {
+                            let s = &d * &ech.rows[i][k] - &c * &ech.rows[r][k];
+                            ech.rows[i][k] = s;
+                        }
+                    }
+                }
+                r += 1;
+            }
+        }
+        ech
+    }
+}
+
+impl<T> DynMatrix<T>
+where
+    T: Zero<Element = T> + One<Element = T> + LCM + Sub<Output = T> + PartialEq + Clone,
+    for<'b> T: Div<&'b T, Output = T>,
+    for<'a, 'b> &'a T: Mul<&'b T, Output = T> + Div<&'b T, Output = T>,
+{
+    /// Iterator over spanning m length row-vectors in the left-nullspace of the matrix.
+    pub fn iter_lnull_span(&self) -> impl Iterator<Item = Vec<T>> {
+        let (m, n) = self.shape();
+        let aug = self.augment();
+        let ech = aug.to_echelon();
+        let subsets: Vec<Vec<T>> = ech
+            .rows
+            .into_iter()
+            .filter_map(|row| {
+                let f = row[0..n].iter().all(|x| x == &T::ZERO);
+                f.then(|| row[n..n + m].to_vec())
+            })
+            .collect();
+        subsets.into_iter()
+    }
+}
+
+impl<T> Conjugate for DynMatrix<T>
+where
+    T: Conjugate<Output = T>,
+{
+    type Output = DynMatrix<T>;
+
+    fn conj(&self) -> DynMatrix<T> {
+        let mut res = self.transpose();
+        for a in res.iter_mut_row_major() {
+            *a = a.conj();
+        }
+        res
+    }
+}
+
+impl<T, const M: usize, const N: usize> From<Matrix<T, M, N>> for DynMatrix<T>
+where
+    T: Clone,
+{
+    fn from(mat: Matrix<T, M, N>) -> Self {
+        let rows = mat.rows.iter().map(|row| row.to_vec()).collect();
+        DynMatrix { rows, m: M, n: N }
+    }
+}
+
+impl<T, const M: usize, const N: usize> TryFrom<DynMatrix<T>> for Matrix<T, M, N>
+where
+    T: Clone,
+{
+    type Error = String;
+
+    fn try_from(mat: DynMatrix<T>) -> Result<Self, String> {
+        if mat.shape() != (M, N) {
+            return Err(format!(
+                "cannot convert {}x{} matrix into {}x{} matrix",
+                mat.m, mat.n, M, N
+            ));
+        }
+        let rows = array_init(|i| array_init(|j| mat.rows[i][j].clone()));
+        Ok(Matrix::from_array(rows))
+    }
+}
+
+impl<T> Display for DynMatrix<T>
+where
+    T: Display + Clone,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let strings: Vec<String> = self.iter_row_major().map(T::to_string).collect();
+        let max_width = strings.iter().map(String::len).max().unwrap_or(0);
+
+        macro_rules! write_end {
+            ($l:literal, $r:literal) => {
+                write!(fmt, $l)?;
+                for _ in 0..self.n {
+                    write!(fmt, "{:>w$} ", "", w = max_width)?;
+                }
+                writeln!(fmt, $r)?;
+            };
+        }
+
+        write_end!("┌ ", "┐");
+        for i in 0..self.m {
+            write!(fmt, "│ ")?;
+            for j in 0..self.n {
+                write!(fmt, "{:>w$} ", strings[j + i * self.n], w = max_width)?;
+            }
+            writeln!(fmt, "│")?;
+        }
+        write_end!("└ ", "┘");
+        Ok(())
+    }
+}
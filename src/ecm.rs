@@ -0,0 +1,204 @@
+use std::ops::{Add, Mul, Sub};
+
+use crate::integers::IntegerRing;
+use crate::pm1::{gcd_factor, primes_to};
+
+/// Number of primes between gcds in stage 2.
+const BATCH: usize = 64;
+
+/// First Suyama parameter $\sigma$ tried, past the degenerate $0, \pm 1, \pm 3, \pm 5$.
+const FIRST_SIGMA: i32 = 6;
+
+/// Residue modulo $n$, in $[0, n)$, which the curve arithmetic runs over.
+#[derive(Clone, Debug, PartialEq)]
+struct Residue<'a, T> {
+    x: T,
+    n: &'a T,
+}
+
+impl<'a, T: IntegerRing> Residue<'a, T> {
+    fn new(x: T, n: &'a T) -> Self {
+        Residue {
+            x: x.mul_mod(&T::ONE, n),
+            n,
+        }
+    }
+
+    fn squared(&self) -> Self {
+        self * self
+    }
+}
+
+impl<'a, T: IntegerRing> Add for &Residue<'a, T> {
+    type Output = Residue<'a, T>;
+
+    fn add(self, rhs: Self) -> Residue<'a, T> {
+        let s = self.x.clone() + rhs.x.clone();
+        let x = if &s >= self.n { s - self.n.clone() } else { s };
+        Residue { x, n: self.n }
+    }
+}
+
+impl<'a, T: IntegerRing> Sub for &Residue<'a, T> {
+    type Output = Residue<'a, T>;
+
+    fn sub(self, rhs: Self) -> Residue<'a, T> {
+        let x = if self.x >= rhs.x {
+            self.x.clone() - rhs.x.clone()
+        } else {
+            self.x.clone() + self.n.clone() - rhs.x.clone()
+        };
+        Residue { x, n: self.n }
+    }
+}
+
+impl<'a, T: IntegerRing> Mul for &Residue<'a, T> {
+    type Output = Residue<'a, T>;
+
+    fn mul(self, rhs: Self) -> Residue<'a, T> {
+        Residue {
+            x: self.x.mul_mod(&rhs.x, self.n),
+            n: self.n,
+        }
+    }
+}
+
+/// Point $(X : Z)$ of a Montgomery curve in projective coordinates, without $Y$.
+#[derive(Clone, Debug)]
+struct Point<'a, T> {
+    x: Residue<'a, T>,
+    z: Residue<'a, T>,
+}
+
+/// Montgomery curve $B y^2 = x^3 + A x^2 + x$ modulo $n$, of which only
+/// $(A + 2) / 4 = a_{24} / d_{24}$ is needed, kept as a fraction to avoid inverses.
+struct Curve<'a, T> {
+    a24: Residue<'a, T>,
+    d24: Residue<'a, T>,
+}
+
+impl<'a, T: IntegerRing> Curve<'a, T> {
+    /// Suyama's parametrization by $\sigma$, with $u = \sigma^2 - 5$ and $v = 4 \sigma$,
+    /// which has a starting point $(u^3 : v^3)$ and a group order divisible by 12.
+    fn suyama(sigma: i32, n: &'a T) -> (Self, Point<'a, T>) {
+        let sigma = T::from(sigma);
+        let u = Residue::new(sigma.clone() * sigma.clone() - T::from(5), n);
+        let v = Residue::new(T::from(4) * sigma, n);
+        let u3 = &u.squared() * &u;
+        let v3 = &v.squared() * &v;
+        let v_u = &v - &u;
+        let u3v = &(&u + &u) + &u;
+        // (A + 2) / 4 = (v - u)^3 (3u + v) / (16 u^3 v)
+        let a24 = &(&v_u.squared() * &v_u) * &(&u3v + &v);
+        let d24 = &(&Residue::new(T::from(16), n) * &u3) * &v;
+        (Curve { a24, d24 }, Point { x: u3, z: v3 })
+    }
+
+    /// $2P$.
+    fn double(&self, p: &Point<'a, T>) -> Point<'a, T> {
+        let s = (&p.x + &p.z).squared();
+        let d = (&p.x - &p.z).squared();
+        let t = &s - &d;
+        Point {
+            x: &(&s * &d) * &self.d24,
+            z: &t * &(&(&d * &self.d24) + &(&t * &self.a24)),
+        }
+    }
+
+    /// $P + Q$, given $P - Q$.
+    fn add(&self, p: &Point<'a, T>, q: &Point<'a, T>, diff: &Point<'a, T>) -> Point<'a, T> {
+        let u = &(&p.x - &p.z) * &(&q.x + &q.z);
+        let v = &(&p.x + &p.z) * &(&q.x - &q.z);
+        Point {
+            x: &diff.z * &(&u + &v).squared(),
+            z: &diff.x * &(&u - &v).squared(),
+        }
+    }
+
+    /// $kP$ for $k \ge 1$, with the Montgomery ladder on $(jP, (j + 1)P)$.
+    fn mul(&self, p: &Point<'a, T>, k: u32) -> Point<'a, T> {
+        let (mut r0, mut r1) = (p.clone(), self.double(p));
+        for i in (0..31 - k.leading_zeros()).rev() {
+            if (k >> i) & 1 == 1 {
+                r0 = self.add(&r1, &r0, p);
+                r1 = self.double(&r1);
+            } else {
+                r1 = self.add(&r1, &r0, p);
+                r0 = self.double(&r0);
+            }
+        }
+        r0
+    }
+}
+
+/// One curve of [`ecm`], with Suyama parameter $\sigma$.
+fn curve<T: IntegerRing>(n: &T, sigma: i32, primes: &[u32], b1: u32, b2: u32) -> Option<T> {
+    let (curve, mut q) = Curve::suyama(sigma, n);
+    let split = primes.iter().take_while(|&&p| p <= b1).count();
+    for &p in &primes[..split] {
+        let mut k = p;
+        while k as u64 * p as u64 <= b1 as u64 {
+            k *= p;
+        }
+        q = curve.mul(&q, k);
+    }
+    match gcd_factor(&q.z.x, n) {
+        Ok(None) => {}
+        Ok(d) => return d,
+        Err(()) => return None,
+    }
+    // stage 2: a prime vw ± u kills Q modulo p exactly when vwQ = ±uQ, i.e. when their x agree
+    // w is at most 2 B_1, so that the first giant step is within w/2 of the primes above B_1
+    let w = ((b2 as f64).sqrt() as u32).min(2 * b1).max(2) & !1;
+    let mut babies = vec![q.clone(), curve.double(&q)];
+    for u in 2..w as usize / 2 {
+        let next = curve.add(&babies[u - 1], &q, &babies[u - 2]);
+        babies.push(next);
+    }
+    let q_w = curve.mul(&q, w);
+    let mut v = (b1 / w).max(1);
+    let (mut giant, mut next) = (curve.mul(&q, v * w), curve.mul(&q, (v + 1) * w));
+    for batch in primes[split..].chunks(BATCH) {
+        let mut acc = Residue::new(T::ONE, n);
+        for &p in batch {
+            while v * w + w / 2 < p {
+                v += 1;
+                let after = curve.add(&next, &q_w, &giant);
+                giant = std::mem::replace(&mut next, after);
+            }
+            // p is odd and w even, so u is never 0
+            let baby = &babies[(p as i64 - (v * w) as i64).unsigned_abs() as usize - 1];
+            acc = &acc * &(&(&giant.x * &baby.z) - &(&baby.x * &giant.z));
+        }
+        match gcd_factor(&acc.x, n) {
+            Ok(None) => {}
+            Ok(d) => return d,
+            Err(()) => return None,
+        }
+    }
+    None
+}
+
+/// Lenstra's elliptic curve method, with Montgomery curves from Suyama's parametrization,
+/// which finds a prime factor $p$ of $n$ if the order of one of the `curves` curves
+/// modulo $p$ is $B_1$-smooth but for (at most) one prime in $(B_1, B_2]$.
+///
+/// Works like [`pm1`](crate::pm1::pm1) in the group of points of the curve,
+/// whose order varies from curve to curve over $[p + 1 - 2\sqrt p, p + 1 + 2\sqrt p]$.
+/// Stage 1 multiplies the starting point by each prime power up to $B_1$, so that it
+/// is the point at infinity modulo $p$ (its $Z \equiv 0$) if the order is $B_1$-smooth.
+/// Stage 2 is a baby-step giant-step over the primes $q = vw \pm u$ in $(B_1, B_2]$,
+/// as $vwQ = \pm uQ$ exactly when their $x$ coordinates $X / Z$ agree.
+///
+/// Returns a non-trivial factor of $n$, if one is found.
+pub fn ecm<T: IntegerRing>(n: &T, b1: u32, b2: u32, curves: usize) -> Option<T> {
+    if n.rem_small(2) == 0 {
+        return (n > &T::from(2)).then_some(T::from(2));
+    }
+    // stage 2 only takes odd primes, for which u is never 0
+    let b1 = b1.max(2);
+    let primes = primes_to(b1.max(b2));
+    (FIRST_SIGMA..)
+        .take(curves)
+        .find_map(|sigma| curve(n, sigma, &primes, b1, b2))
+}
@@ -0,0 +1,119 @@
+use crate::coprime_base::coprime_base;
+use crate::ecm::ecm;
+use crate::integers::IntegerRing;
+use crate::perfect_power::perfect_power;
+use crate::pm1::{pm1, pp1};
+use crate::primality::is_prime;
+use crate::primes::primes_in;
+use crate::quadratic_sieve::qs_auto;
+use crate::rho::rho;
+
+/// Primes below which factors are found by trial division.
+const TRIAL_DIVISION_BOUND: i32 = 1000;
+
+/// Bound on the steps of Pollard's rho method before moving on to the other methods,
+/// which finds most factors up to around $10^{10}$.
+const RHO_ITERATIONS: usize = 1 << 16;
+
+/// Stage 1 and 2 bounds for the $p - 1$ and $p + 1$ methods, which are cheap next to the sieve
+/// and find the larger factors $p$ for which $p \mp 1$ happens to be smooth.
+const PM1_B1: u32 = 10_000;
+const PM1_B2: u32 = 500_000;
+
+/// Levels of the elliptic curve method, as the size in digits of the factors each is
+/// aimed at, with its $B_1$ (and $B_2 = 100 B_1$) and number of curves.
+/// A level is only run on cofactors with at least three times as many digits,
+/// below which the sieve is quicker.
+const ECM_LEVELS: [(usize, u32, usize); 3] = [(15, 2_000, 25), (20, 11_000, 90), (25, 50_000, 300)];
+
+/// Complete factorization of $|n|$ into primes, as (prime, exponent) pairs in increasing order.
+///
+/// Small factors are removed by trial division, and the cofactor is split
+/// (after checking for perfect powers) by [`rho`], then [`pm1`] and [`pp1`],
+/// then [`ecm`] for medium sized factors, or by [`qs_auto`] if there are none,
+/// until every factor passes [`is_prime`], which is deterministic below $2^{64}$.
+/// The parts of each split are refined, along with every factor found so far, into a
+/// [`coprime_base`], so that the factors stay pairwise coprime and no prime is found twice.
+///
+/// Returns an empty factorization for $0$ and $\pm 1$.
+///
+/// # Panics
+///
+/// If the sieve fails to split a composite factor, for example when `T`
+/// is too narrow to sieve it.
+pub fn factorize<T: IntegerRing>(n: T) -> Vec<(T, u32)> {
+    let mut n = n.abs();
+    let mut factors = Vec::new();
+    if n == T::ZERO {
+        return factors;
+    }
+    for p in primes_in(0..TRIAL_DIVISION_BOUND as u64) {
+        let p = p as i32;
+        let mut e = 0;
+        while n.rem_small(p as u32) == 0 {
+            n = n / T::from(p);
+            e += 1;
+        }
+        if e > 0 {
+            factors.push((T::from(p), e));
+        }
+    }
+    // Primes found by splitting, and cofactors left to split, with their multiplicities,
+    // which together are kept a coprime base
+    let mut found: Vec<(T, u32)> = Vec::new();
+    let mut composites = vec![(n, 1)];
+    while let Some((m, e)) = composites.pop() {
+        if m == T::ONE {
+            continue;
+        }
+        let bound = T::from(TRIAL_DIVISION_BOUND);
+        if m < bound.clone() * bound || is_prime(&m) {
+            found.push((m, e));
+            continue;
+        }
+        let (r, k) = perfect_power(&m);
+        if k > 1 {
+            composites.push((r, e * k));
+            continue;
+        }
+        let d = rho(&m, RHO_ITERATIONS)
+            .or_else(|| pm1(&m, PM1_B1, PM1_B2))
+            .or_else(|| pp1(&m, PM1_B1, PM1_B2))
+            .or_else(|| {
+                let digits = m.to_string().len();
+                ECM_LEVELS
+                    .iter()
+                    .take_while(|&&(d, _, _)| 3 * d <= digits)
+                    .find_map(|&(_, b1, curves)| ecm(&m, b1, 100 * b1, curves))
+            });
+        let (a, b) = match d {
+            Some(d) => (m.clone() / d.clone(), d),
+            None => {
+                qs_auto(m.clone()).unwrap_or_else(|err| panic!("failed to split {}: {}", m, err))
+            }
+        };
+        // the parts of a split can share factors (when m isn't square-free), so they are
+        // refined along with everything else into a new coprime base, whose primes stay found
+        let primes: Vec<T> = found.iter().map(|(p, _)| p.clone()).collect();
+        let mut parts: Vec<(T, u32)> = found.drain(..).chain(composites.drain(..)).collect();
+        parts.push((a, e));
+        parts.push((b, e));
+        let ns: Vec<T> = parts.iter().map(|(m, _)| m.clone()).collect();
+        let (base, exponents) = coprime_base(&ns);
+        for (j, b) in base.into_iter().enumerate() {
+            let e = parts
+                .iter()
+                .zip(&exponents)
+                .map(|((_, e), x)| e * x[j])
+                .sum();
+            if primes.contains(&b) {
+                found.push((b, e));
+            } else {
+                composites.push((b, e));
+            }
+        }
+    }
+    factors.extend(found);
+    factors.sort();
+    factors
+}
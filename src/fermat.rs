@@ -0,0 +1,35 @@
+use crate::integers::IntegerRing;
+
+/// Fermat's method, which writes an odd $n$ as a difference of squares
+/// $a^2 - b^2 = (a + b)(a - b)$ with the least $a \ge \sqrt n$.
+///
+/// Steps through $a$ from $\lceil \sqrt n \rceil$, keeping $a^2 - n$ up to date by adding $2a + 1$,
+/// until it is a square. For $n = pq$ with $p \ge q$ that takes $(p + q)/2 - \sqrt n$ steps,
+/// so it is quick only when the factors are close together, and slower than trial division
+/// when they are not (for a prime $n$ it takes about $n / 2$ steps, to find $n \cdot 1$).
+///
+/// Returns the factors $(a + b, a - b)$, which are $(n, 1)$ for a prime $n$.
+///
+/// # Panics
+///
+/// If $n$ is even, for which $a^2 - n$ might never be a square.
+pub fn fermat<T: IntegerRing>(n: &T) -> (T, T) {
+    assert!(
+        n.rem_small(2) == 1,
+        "Fermat's method needs an odd n, not {}",
+        n
+    );
+    let mut a = n.sqrt();
+    if &(a.clone() * a.clone()) < n {
+        a = a + T::ONE;
+    }
+    let mut r = a.clone() * a.clone() - n.clone();
+    loop {
+        let b = r.sqrt();
+        if b.clone() * b.clone() == r {
+            return (a.clone() + b.clone(), a - b);
+        }
+        r = r + T::from(2) * a.clone() + T::ONE;
+        a = a + T::ONE;
+    }
+}
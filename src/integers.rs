@@ -171,22 +171,68 @@ pub trait Integer: Zero<Element = Self> + One<Element = Self>
 where
     Self: Sized,
 {
+    /// Number of bits available for the magnitude (not counting the sign bit).
+    const BITS: u32;
+
     fn abs(&self) -> Self;
     //fn rem_euclid(&self, m: &Self) -> Self;
     fn pow(&self, e: u32) -> Self;
     fn log2(&self) -> Self;
     //fn bit;
-    fn bit_length(&self) -> Self;
+
+    /// Number of bits in the magnitude (0 for zero).
+    fn bit_length(&self) -> u32;
     fn pow_mod(&self, e: u32, m: Self) -> Self;
     //fn sqrt_mod(&self, m: Self) -> Self;
 
+    /// Product modulo `m` (in $[0, m)$), without overflowing on the intermediate product.
+    fn mul_mod(&self, rhs: &Self, m: &Self) -> Self;
+
+    /// Integer square root (the floor of the square root).
+    fn sqrt(&self) -> Self;
+
+    /// Least non-negative residue modulo a machine word.
+    fn rem_small(&self, m: u32) -> u32;
+
     fn squared(&self) -> Self {
         self.pow(2)
     }
 }
 
+/// Integers over which the sieve (and friends) can operate.
+pub trait IntegerRing = Integer
+    + GCD
+    + From<i32>
+    + Ord
+    + Clone
+    + Display
+    + std::fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + std::ops::Neg<Output = Self>;
+
+/// Modular exponentiation with an exponent of the same (possibly multiprecision) type,
+/// for when the exponent doesn't fit the `u32` of [`Integer::pow_mod`].
+pub fn pow_mod_int<T: IntegerRing>(b: &T, e: &T, m: &T) -> T {
+    let two = T::from(2);
+    let mut b = b.mul_mod(&T::ONE, m);
+    let mut e = e.clone();
+    let mut res = T::ONE.mul_mod(&T::ONE, m);
+    while e > T::ZERO {
+        if e.rem_small(2) == 1 {
+            res = res.mul_mod(&b, m);
+        }
+        e = e / two.clone();
+        b = b.mul_mod(&b, m);
+    }
+    res
+}
+
 macro_rules! impl_integer {
-    ($T:ty) => {
+    ($T:ty, $W:ty) => {
         fn pow(&self, e: u32) -> Self {
             <$T>::pow(self.clone(), e)
         }
@@ -196,48 +242,69 @@ macro_rules! impl_integer {
         fn log2(&self) -> Self {
             (*self as f32).log2() as Self
         }
-        fn bit_length(&self) -> Self {
-            self.abs().log2() + 1
+        fn bit_length(&self) -> u32 {
+            128 - (*self as i128).unsigned_abs().leading_zeros()
         }
         fn pow_mod(&self, mut e: u32, m: Self) -> Self {
-            let mut b = self % &m;
+            let mut b = self.rem_euclid(m);
             let mut res = 1;
             while e > 0 {
                 if &e % 2 == 1 {
-                    res = (res * &b) % &m;
+                    res = res.mul_mod(&b, &m);
                 }
                 e = e >> 1;
-                b = (&b * &b) % &m;
+                b = b.mul_mod(&b, &m);
             }
             res
         }
+        fn mul_mod(&self, rhs: &Self, m: &Self) -> Self {
+            ((*self as $W) * (*rhs as $W)).rem_euclid(*m as $W) as $T
+        }
+        fn sqrt(&self) -> Self {
+            assert!(*self == self.abs(), "square root of negative integer");
+            let mut r = (*self as f64).sqrt() as $T;
+            while r.checked_mul(r).map_or(true, |s| s > *self) {
+                r -= 1;
+            }
+            while (r + 1).checked_mul(r + 1).map_or(false, |s| s <= *self) {
+                r += 1;
+            }
+            r
+        }
+        fn rem_small(&self, m: u32) -> u32 {
+            (*self as i128).rem_euclid(m as i128) as u32
+        }
     };
 }
 
 macro_rules! impl_integer_signed {
-    ($T:ty) => {
+    ($T:ty, $W:ty) => {
         impl Integer for $T {
+            const BITS: u32 = <$T>::BITS - 1;
+
             fn abs(&self) -> Self {
                 <$T>::abs(self.clone())
             }
-            impl_integer!($T);
+            impl_integer!($T, $W);
         }
     };
-    ($($T:ty),*) => { $( impl_integer_signed!($T); )* };
+    ($(($T:ty, $W:ty)),*) => { $( impl_integer_signed!($T, $W); )* };
 }
 
 macro_rules! impl_integer_unsigned {
-    ($T:ty) => {
+    ($T:ty, $W:ty) => {
         impl Integer for $T
         {
+            const BITS: u32 = <$T>::BITS;
+
             fn abs(&self) -> Self {
                 self.clone()
             }
-            impl_integer!($T);
+            impl_integer!($T, $W);
         }
     };
-    ($($T:ty),*) => { $( impl_integer_unsigned!($T); )* };
+    ($(($T:ty, $W:ty)),*) => { $( impl_integer_unsigned!($T, $W); )* };
 }
 
-impl_integer_signed!(i8, i16, i32, i64);
-impl_integer_unsigned!(u8, u16, u32, u64);
+impl_integer_signed!((i8, i16), (i16, i32), (i32, i64), (i64, i128));
+impl_integer_unsigned!((u8, u16), (u16, u32), (u32, u64), (u64, u128));
@@ -0,0 +1,67 @@
+use crate::integers::IntegerRing;
+
+/// $(2 / n)$ for an odd $n$, which is $(-1)^{(n^2 - 1)/8}$.
+fn two_over<T: IntegerRing>(n: &T) -> i32 {
+    match n.rem_small(8) {
+        3 | 5 => -1,
+        _ => 1,
+    }
+}
+
+/// Jacobi symbol $(a / n)$ over an odd $n > 0$, by binary quadratic reciprocity:
+/// powers of two are taken out of $a$ with $(2 / n)$, then for odd $a$ and $n$,
+/// $(a / n) = (-1)^{\frac{a-1}{2} \frac{n-1}{2}} (n / a)$ and $n$ is reduced modulo $a$.
+///
+/// It is 0 if $a$ and $n$ share a factor, and for a prime $n$ it is the Legendre symbol.
+///
+/// # Panics
+///
+/// If `n` is not odd and positive.
+pub fn jacobi<T: IntegerRing>(a: &T, n: &T) -> i32 {
+    assert!(
+        n > &T::ZERO && n.rem_small(2) == 1,
+        "Jacobi symbol over {}, which is not odd and positive",
+        n
+    );
+    let mut a = a.clone() % n.clone();
+    if a < T::ZERO {
+        a = a + n.clone();
+    }
+    let mut n = n.clone();
+    let mut res = 1;
+    while a != T::ZERO {
+        while a.rem_small(2) == 0 {
+            a = a / T::from(2);
+            res *= two_over(&n);
+        }
+        std::mem::swap(&mut a, &mut n);
+        if a.rem_small(4) == 3 && n.rem_small(4) == 3 {
+            res = -res;
+        }
+        a = a % n.clone();
+    }
+    if n == T::ONE {
+        res
+    } else {
+        0
+    }
+}
+
+/// Kronecker symbol $(a / n)$, extending [`jacobi`] to every $n$ with
+/// $(a / -1) = \pm 1$ for the sign of $a$, $(a / 2) = (2 / a)$ for odd $a$ (and 0 for even $a$),
+/// and $(a / 0) = 1$ if $a = \pm 1$ (and 0 otherwise).
+pub fn kronecker<T: IntegerRing>(a: &T, n: &T) -> i32 {
+    if n == &T::ZERO {
+        return if a.abs() == T::ONE { 1 } else { 0 };
+    }
+    let mut res = if n < &T::ZERO && a < &T::ZERO { -1 } else { 1 };
+    let mut n = n.abs();
+    while n.rem_small(2) == 0 {
+        if a.rem_small(2) == 0 {
+            return 0;
+        }
+        n = n / T::from(2);
+        res *= two_over(a);
+    }
+    res * jacobi(a, &n)
+}
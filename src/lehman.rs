@@ -0,0 +1,47 @@
+use crate::integers::{Integer, GCD};
+
+/// Floor of the cube root.
+fn cbrt(n: u64) -> u64 {
+    let mut r = (n as f64).cbrt() as u64;
+    while r.checked_pow(3).is_none_or(|c| c > n) {
+        r -= 1;
+    }
+    while (r + 1).checked_pow(3).is_some_and(|c| c <= n) {
+        r += 1;
+    }
+    r
+}
+
+/// Lehman's method for a 64 bit $n$, in $O(n^{1/3})$ steps on machine words.
+///
+/// After trial division up to $n^{1/3}$, an $n$ which is not prime is $pq$ with both
+/// factors above $n^{1/3}$, and then $a^2 - 4kn = b^2$ for some $k \le n^{1/3}$ and
+/// $\sqrt{4kn} \le a \le \sqrt{4kn} + n^{1/6} / (4 \sqrt k)$, where $\gcd(a + b, n)$ is a factor.
+/// Only the $k$ with $4kn < 2^{62}$ are tried (so that $a^2$ fits),
+/// which is all of them below $2^{45}$.
+///
+/// Returns a non-trivial factor of $n$, if one is found (and always if $n < 2^{45}$ is composite).
+pub fn lehman(n: u64) -> Option<u64> {
+    let c = cbrt(n);
+    if let Some(d) = (2..=c.max(2)).find(|&d| n.is_multiple_of(d)) {
+        return (d < n).then_some(d);
+    }
+    let sixth = (n as f64).powf(1.0 / 6.0);
+    for k in 1..=c {
+        let four_kn = n.checked_mul(4 * k).filter(|&m| m < 1 << 62)?;
+        let r = four_kn.sqrt();
+        let a_min = if r * r == four_kn { r } else { r + 1 };
+        let a_max = ((four_kn as f64).sqrt() + sixth / (4.0 * (k as f64).sqrt())) as u64;
+        for a in a_min..=a_max {
+            let b2 = a * a - four_kn;
+            let b = b2.sqrt();
+            if b * b == b2 {
+                let d = n.gcd(a + b);
+                if d != 1 && d != n {
+                    return Some(d);
+                }
+            }
+        }
+    }
+    None
+}
@@ -9,26 +9,50 @@
 #![recursion_limit = "10"]
 #![allow(incomplete_features)]
 
+pub mod bigint;
+pub mod bit_matrix;
 pub mod complex;
 pub mod congruence_class;
 pub mod conjugate;
+pub mod coprime_base;
+pub mod dixon;
+pub mod dyn_matrix;
+pub mod ecm;
 pub mod etc;
+pub mod factorize;
+pub mod fermat;
 pub mod identity;
 pub mod integers;
+pub mod jacobi;
+pub mod lehman;
 pub mod matrix;
+pub mod perfect_power;
+pub mod pm1;
+pub mod primality;
+pub mod primes;
 pub mod quadratic_sieve;
 pub mod quotient_group;
+pub mod rho;
+pub mod squfof;
 
 pub mod prelude {
     #[rustfmt::skip]
     pub use crate::{
         {cc, cc_array}, // CongruenceClass macro
+        bit_matrix::BitMatrix,
         congruence_class::CongruenceClass,
         conjugate::Conjugate,
+        dyn_matrix::DynMatrix,
+        factorize::factorize,
         identity::{One, Zero},
-        integers::{Inverse, GCD, LCM, Integer},
+        integers::{Inverse, GCD, LCM, Integer, IntegerRing},
+        jacobi::{jacobi, kronecker},
         matrix::Matrix,
+        primality::is_prime,
         quotient_group::QuotientGroup,
-        quadratic_sieve::qs,
+        quadratic_sieve::{
+            mpqs, qs, qs_auto, qs_with_stats, siqs, siqs_with_stats, QsError, QsParams, Stats,
+            DEFAULT_FUDGE,
+        },
     };
 }
@@ -4,7 +4,8 @@
 #![allow(unused_macros)]
 #![allow(unused_variables)]
 
-//use quadratic_sieve::prelude::*;
+use quadratic_sieve::bigint::I512;
+use quadratic_sieve::prelude::*;
 //use quadratic_sieve::complex::Complex;
 //use itertools::iproduct;
 
@@ -17,14 +18,21 @@
  */
 
 fn main() {
-    /*
-     *let mut args = std::env::args().skip(1);
-     *let n: i32 = args.next().unwrap().parse().unwrap();
-     *let b: usize = args.next().unwrap().parse().unwrap();
-     *let i: usize = args.next().unwrap().parse().unwrap();
-     *match qs(n, b, i) {
-     *    Ok((a, b)) => println!("{} {}", a, b),
-     *    Err(e) => println!("{}", e),
-     *}
-     */
+    let mut args = std::env::args().skip(1);
+    let n: I512 = args.next().unwrap().parse().unwrap();
+    let res = match args.next() {
+        Some(b) => {
+            let b: usize = b.parse().unwrap();
+            let i: usize = args.next().unwrap().parse().unwrap();
+            let t: f64 = args.next().map_or(DEFAULT_FUDGE, |t| t.parse().unwrap());
+            let (res, stats) = qs_with_stats(n, b, i, t);
+            println!("{}", stats);
+            res
+        }
+        None => qs_auto(n),
+    };
+    match res {
+        Ok((a, b)) => println!("{} {}", a, b),
+        Err(e) => println!("{}", e),
+    }
 }
@@ -0,0 +1,58 @@
+use crate::integers::IntegerRing;
+use crate::primality::is_prime;
+
+/// Floor of the k'th root of $n \ge 0$, with Newton's method.
+///
+/// The $n / x^{k-1}$ of each step is taken by dividing by $x$ repeatedly,
+/// so that nothing larger than $n$ is computed.
+pub fn nth_root<T: IntegerRing>(n: &T, k: u32) -> T {
+    if n <= &T::ONE || k == 1 {
+        return n.clone();
+    }
+    let bits = n.bit_length();
+    // start above the root, from where the iteration decreases monotonically to it
+    // (and reaching 1 means that is the root)
+    let mut x = T::from(2).pow(bits / k + 1);
+    let k_t = T::from(k as i32);
+    while x > T::ONE {
+        let q = (1..k).fold(n.clone(), |q, _| q / x.clone());
+        let y = (x.clone() * (k_t.clone() - T::ONE) + q) / k_t.clone();
+        if y >= x {
+            break;
+        }
+        x = y;
+    }
+    x
+}
+
+/// The $r$ with $r^k = n$, if $n$ is a perfect k'th power.
+fn exact_root<T: IntegerRing>(n: &T, k: u32) -> Option<T> {
+    let r = nth_root(n, k);
+    (&r.pow(k) == n).then_some(r)
+}
+
+/// Write $n \ge 2$ as $r^k$ with the largest $k$, returning $(r, k)$, which is $(n, 1)$
+/// if $n$ is not a perfect power.
+///
+/// As in Bernstein's "Detecting perfect powers in essentially linear time", only prime
+/// exponents $p$ need trying, up to $\log_2 n$ (as $r \ge 2$): when $n = r^p$, the largest
+/// exponent of $n$ is $p$ times that of $r$. Each is checked with an exact integer root,
+/// rather than the floating point roots which lose precision for large $n$.
+pub fn perfect_power<T: IntegerRing>(n: &T) -> (T, u32) {
+    let bits = n.bit_length() as i32;
+    (2..bits)
+        .filter(is_prime)
+        .find_map(|p| exact_root(n, p as u32).map(|r| (r, p as u32)))
+        .map_or_else(
+            || (n.clone(), 1),
+            |(r, p)| {
+                let (s, k) = perfect_power(&r);
+                (s, k * p)
+            },
+        )
+}
+
+/// If $n \ge 2$ is $r^k$ for some $k \ge 2$.
+pub fn is_perfect_power<T: IntegerRing>(n: &T) -> bool {
+    perfect_power(n).1 > 1
+}
@@ -0,0 +1,222 @@
+use crate::integers::IntegerRing;
+use crate::primes::primes_in;
+
+/// Number of primes between gcds, in both stages.
+const BATCH: usize = 64;
+
+/// Starting values $A$ which [`pp1`] tries. Each finds $p$ when $p + 1$ is smooth if $A^2 - 4$
+/// is not a square modulo $p$ (and otherwise when $p - 1$ is), so these are chosen with
+/// $A^2 - 4 = 5, 12, 32$ having different square-free parts.
+const PP1_SEEDS: [i32; 3] = [3, 4, 6];
+
+/// Primes up to `b`.
+pub(crate) fn primes_to(b: u32) -> Vec<u32> {
+    primes_in(0..b as u64 + 1)
+        .into_iter()
+        .map(|p| p as u32)
+        .collect()
+}
+
+/// $a - b$ for $a, b \in [0, n)$, in $[0, n)$.
+fn sub_mod<T: IntegerRing>(a: &T, b: &T, n: &T) -> T {
+    if a >= b {
+        a.clone() - b.clone()
+    } else {
+        a.clone() + n.clone() - b.clone()
+    }
+}
+
+/// Non-trivial factor from $\gcd(x, n)$, if there is one, or `Err` if the gcd is $n$.
+pub(crate) fn gcd_factor<T: IntegerRing>(x: &T, n: &T) -> Result<Option<T>, ()> {
+    let g = x.clone().gcd(n.clone());
+    if &g == n {
+        Err(())
+    } else if g == T::ONE {
+        Ok(None)
+    } else {
+        Ok(Some(g))
+    }
+}
+
+/// Stage 1 over the primes $p \le B_1$, where `step(x, p)` takes $x$ to $x^p$ (in the
+/// group in question) and `gcd` gives a factor from the current $x$.
+///
+/// Steps by the highest power of $p$ at most $B_1$, and takes a gcd after every
+/// [`BATCH`] primes. If a batch gives all of $n$ it is retraced one step at a time,
+/// in case the orders modulo different primes were completed in the same batch.
+fn stage1<T: IntegerRing>(
+    mut x: T,
+    primes: &[u32],
+    b1: u32,
+    step: impl Fn(&T, u32) -> T,
+    gcd: impl Fn(&T) -> Result<Option<T>, ()>,
+) -> Result<T, Option<T>> {
+    for batch in primes.chunks(BATCH) {
+        let start = x.clone();
+        for &p in batch {
+            let mut q = p;
+            while q as u64 * p as u64 <= b1 as u64 {
+                q *= p;
+            }
+            x = step(&x, q);
+        }
+        match gcd(&x) {
+            Ok(None) => continue,
+            Ok(Some(d)) => return Err(Some(d)),
+            Err(()) => {
+                x = start;
+                for &p in batch {
+                    let mut q = p;
+                    while q <= b1 {
+                        x = step(&x, p);
+                        match gcd(&x) {
+                            Ok(None) => {}
+                            Ok(d) => return Err(d),
+                            Err(()) => return Err(None),
+                        }
+                        q = q.saturating_mul(p);
+                    }
+                }
+            }
+        }
+    }
+    Ok(x)
+}
+
+/// Pollard's $p - 1$ method, which finds a prime factor $p$ of $n$
+/// if $p - 1$ is $B_1$-smooth but for (at most) one prime in $(B_1, B_2]$.
+///
+/// Stage 1 raises $a = 2$ to each prime power up to $B_1$, so that $a \equiv 1 \pmod p$
+/// if $p - 1$ is $B_1$-smooth. Stage 2 looks for a prime $q \in (B_1, B_2]$ with $a^q \equiv 1$,
+/// by baby-step giant-step: writing $q = vw - u$ with $0 \le u < w$, this is $a^{vw} \equiv a^u$,
+/// so the products of $a^{vw} - a^u$ over all such $q$ are gcd'ed with $n$.
+///
+/// Returns a non-trivial factor of $n$, if one is found.
+pub fn pm1<T: IntegerRing>(n: &T, b1: u32, b2: u32) -> Option<T> {
+    if n.rem_small(2) == 0 {
+        return (n > &T::from(2)).then_some(T::from(2));
+    }
+    let primes = primes_to(b1.max(b2));
+    let split = primes.iter().take_while(|&&p| p <= b1).count();
+    let a = stage1(
+        T::from(2),
+        &primes[..split],
+        b1,
+        |a, q| a.pow_mod(q, n.clone()),
+        |a| gcd_factor(&sub_mod(a, &T::ONE, n), n),
+    );
+    let a = match a {
+        Ok(a) => a,
+        Err(d) => return d,
+    };
+    let w = ((b2 as f64).sqrt() as u32).max(1);
+    let mut babies = vec![T::ONE];
+    for u in 1..w as usize {
+        babies.push(babies[u - 1].mul_mod(&a, n));
+    }
+    let a_w = a.pow_mod(w, n.clone());
+    let mut v = (b1 / w).max(1);
+    let mut giant = a.pow_mod(v * w, n.clone());
+    for batch in primes[split..].chunks(BATCH) {
+        let mut acc = T::ONE;
+        for &q in batch {
+            while v * w < q {
+                v += 1;
+                giant = giant.mul_mod(&a_w, n);
+            }
+            let u = (v * w - q) as usize;
+            acc = acc.mul_mod(&sub_mod(&giant, &babies[u], n), n);
+        }
+        match gcd_factor(&acc, n) {
+            Ok(None) => {}
+            Ok(d) => return d,
+            Err(()) => return None,
+        }
+    }
+    None
+}
+
+/// The Lucas sequence $V_k$ of $A$ (with $V_0 = 2$, $V_1 = A$ and $V_{j+1} = A V_j - V_{j-1}$)
+/// modulo $n$, with a Montgomery ladder on the pair $(V_j, V_{j+1})$.
+fn lucas_v<T: IntegerRing>(a: &T, k: u32, n: &T) -> T {
+    if k == 0 {
+        return T::from(2).mul_mod(&T::ONE, n);
+    }
+    let two = T::from(2);
+    let (mut x, mut y) = (a.clone(), sub_mod(&a.mul_mod(a, n), &two, n));
+    for i in (0..31 - k.leading_zeros()).rev() {
+        // V_2j = V_j^2 - 2 and V_2j+1 = V_j V_j+1 - A
+        let xy = sub_mod(&x.mul_mod(&y, n), a, n);
+        if (k >> i) & 1 == 1 {
+            x = xy;
+            y = sub_mod(&y.mul_mod(&y, n), &two, n);
+        } else {
+            y = xy;
+            x = sub_mod(&x.mul_mod(&x, n), &two, n);
+        }
+    }
+    x
+}
+
+/// Williams' $p + 1$ method, which finds a prime factor $p$ of $n$
+/// if $p + 1$ is $B_1$-smooth but for (at most) one prime in $(B_1, B_2]$.
+///
+/// Works like [`pm1`] in the group of norm 1 elements $\alpha$ of $\mathbb{F}_{p^2}$,
+/// represented by the traces $V_k = \alpha^k + \alpha^{-k}$ (a Lucas sequence),
+/// which has order $p + 1$ when $A^2 - 4$ is not a square modulo $p$.
+/// Stage 1 computes $V_k$ for $k$ the product of the prime powers up to $B_1$
+/// (since $V_{jk}(A) = V_j(V_k(A))$), where $V_k \equiv 2 \pmod p$ means $\alpha^k = 1$.
+/// Stage 2 is the baby-step giant-step of [`pm1`], with $q = vw \pm u$ for $0 \le u \le w/2$,
+/// as $V_{vw} \equiv V_u$ exactly when $\alpha^{vw + u}$ or $\alpha^{vw - u}$ is 1.
+///
+/// Returns a non-trivial factor of $n$, if one is found.
+pub fn pp1<T: IntegerRing>(n: &T, b1: u32, b2: u32) -> Option<T> {
+    if n.rem_small(2) == 0 {
+        return (n > &T::from(2)).then_some(T::from(2));
+    }
+    let primes = primes_to(b1.max(b2));
+    let split = primes.iter().take_while(|&&p| p <= b1).count();
+    let two = T::from(2);
+    'seeds: for &seed in PP1_SEEDS.iter() {
+        let a = stage1(
+            T::from(seed).mul_mod(&T::ONE, n),
+            &primes[..split],
+            b1,
+            |a, q| lucas_v(a, q, n),
+            |a| gcd_factor(&sub_mod(a, &two, n), n),
+        );
+        let a = match a {
+            Ok(a) => a,
+            Err(Some(d)) => return Some(d),
+            Err(None) => continue,
+        };
+        // w is at most 2 B_1, so that the first giant step is within w/2 of the primes above B_1
+        let w = ((b2 as f64).sqrt() as u32).min(2 * b1).max(2) & !1;
+        let mut babies = vec![T::from(2).mul_mod(&T::ONE, n), a.clone()];
+        for u in 2..=w as usize / 2 {
+            let next = sub_mod(&a.mul_mod(&babies[u - 1], n), &babies[u - 2], n);
+            babies.push(next);
+        }
+        let v_w = lucas_v(&a, w, n);
+        let mut v = (b1 / w).max(1);
+        let (mut prev, mut giant) = (lucas_v(&a, (v - 1) * w, n), lucas_v(&a, v * w, n));
+        for batch in primes[split..].chunks(BATCH) {
+            let mut acc = T::ONE;
+            for &q in batch {
+                while v * w + w / 2 < q {
+                    v += 1;
+                    let next = sub_mod(&v_w.mul_mod(&giant, n), &prev, n);
+                    prev = std::mem::replace(&mut giant, next);
+                }
+                let u = (q as i64 - (v * w) as i64).unsigned_abs() as usize;
+                acc = acc.mul_mod(&sub_mod(&giant, &babies[u], n), n);
+            }
+            match gcd_factor(&acc, n) {
+                Ok(None) => {}
+                Ok(d) => return d,
+                Err(()) => continue 'seeds,
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,145 @@
+use crate::integers::{pow_mod_int, IntegerRing};
+use crate::jacobi::jacobi;
+
+/// Primes to trial divide by before the probable prime tests.
+const SMALL_PRIMES: [i32; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Miller-Rabin witnesses which are deterministic for $n < 3215031751$ (so all 31 bit $n$).
+const WITNESSES_31: [i32; 4] = [2, 3, 5, 7];
+
+/// Miller-Rabin witnesses which are deterministic for $n < 2^{64}$ (due to Jim Sinclair).
+const WITNESSES_64: [i32; 7] = [2, 325, 9375, 28178, 450775, 9780504, 1795265022];
+
+/// Reduce into $[0, n)$, whatever the sign convention of `%`.
+fn reduce<T: IntegerRing>(x: T, n: &T) -> T {
+    let r = x % n.clone();
+    if r < T::ZERO {
+        r + n.clone()
+    } else {
+        r
+    }
+}
+
+/// Halve modulo an odd $n$.
+fn half_mod<T: IntegerRing>(x: T, n: &T) -> T {
+    if x.rem_small(2) == 1 {
+        (x + n.clone()) / T::from(2)
+    } else {
+        x / T::from(2)
+    }
+}
+
+/// Express $m$ as $d 2^s$ with $d$ odd.
+fn split_twos<T: IntegerRing>(m: &T) -> (T, u32) {
+    let mut d = m.clone();
+    let mut s = 0;
+    while d.rem_small(2) == 0 {
+        d = d / T::from(2);
+        s += 1;
+    }
+    (d, s)
+}
+
+/// Strong probable prime test of an odd $n > 2$ to base $a$ (Miller-Rabin).
+///
+/// Bases divisible by $n$ pass trivially.
+pub fn is_strong_probable_prime<T: IntegerRing>(n: &T, a: &T) -> bool {
+    let a = reduce(a.clone(), n);
+    if a == T::ZERO {
+        return true;
+    }
+    let n_1 = n.clone() - T::ONE;
+    let (d, s) = split_twos(&n_1);
+    let mut x = pow_mod_int(&a, &d, n);
+    if x == T::ONE || x == n_1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = x.mul_mod(&x, n);
+        if x == n_1 {
+            return true;
+        }
+    }
+    false
+}
+
+/// Strong Lucas probable prime test of an odd $n > 2$ which is not a square,
+/// with Selfridge's parameters: the first $D$ in $5, -7, 9, -11, \ldots$ with $(D / n) = -1$,
+/// $P = 1$ and $Q = (1 - D) / 4$.
+pub fn is_strong_lucas_probable_prime<T: IntegerRing>(n: &T) -> bool {
+    let mut d = 5;
+    loop {
+        match jacobi(&T::from(d), n) {
+            -1 => break,
+            // unless |D| = n, D shares a factor with n
+            0 if T::from(d.abs()) != *n => return false,
+            _ => d = if d > 0 { -d - 2 } else { -d + 2 },
+        }
+    }
+    let big_d = reduce(T::from(d), n);
+    let q = reduce(T::from((1 - d) / 4), n);
+    // n + 1 = k 2^s with k odd, for the Lucas sequences U_k and V_k (with P = 1)
+    let (k, s) = split_twos(&(n.clone() + T::ONE));
+    let mut bits = Vec::new();
+    let mut m = k;
+    while m > T::ZERO {
+        bits.push(m.rem_small(2) == 1);
+        m = m / T::from(2);
+    }
+    let (mut u, mut v, mut q_k) = (T::ONE, T::ONE, q.clone());
+    for &bit in bits.iter().rev().skip(1) {
+        // U_2j = U_j V_j, V_2j = V_j^2 - 2 Q^j
+        u = u.mul_mod(&v, n);
+        v = reduce(v.mul_mod(&v, n) - q_k.clone() - q_k.clone(), n);
+        q_k = q_k.mul_mod(&q_k, n);
+        if bit {
+            // U_j+1 = (P U_j + V_j) / 2, V_j+1 = (D U_j + P V_j) / 2
+            let u_1 = half_mod(reduce(u.clone() + v.clone(), n), n);
+            v = half_mod(reduce(big_d.mul_mod(&u, n) + v, n), n);
+            u = u_1;
+            q_k = q_k.mul_mod(&q, n);
+        }
+    }
+    if u == T::ZERO || v == T::ZERO {
+        return true;
+    }
+    for _ in 1..s {
+        v = reduce(v.mul_mod(&v, n) - q_k.clone() - q_k.clone(), n);
+        q_k = q_k.mul_mod(&q_k, n);
+        if v == T::ZERO {
+            return true;
+        }
+    }
+    false
+}
+
+/// If $n$ is prime.
+///
+/// Deterministic Miller-Rabin (with fixed witness sets) for $n < 2^{64}$,
+/// and the Baillie-PSW test (a base 2 strong probable prime test and a strong Lucas test)
+/// above, for which no composite which passes is known.
+pub fn is_prime<T: IntegerRing>(n: &T) -> bool {
+    if n < &T::from(2) {
+        return false;
+    }
+    for &p in SMALL_PRIMES.iter() {
+        if n.rem_small(p as u32) == 0 {
+            return n == &T::from(p);
+        }
+    }
+    let bits = n.bit_length();
+    if bits <= 31 {
+        WITNESSES_31
+            .iter()
+            .all(|&a| is_strong_probable_prime(n, &T::from(a)))
+    } else if bits <= 64 {
+        WITNESSES_64
+            .iter()
+            .all(|&a| is_strong_probable_prime(n, &T::from(a)))
+    } else {
+        let r = n.sqrt();
+        is_strong_probable_prime(n, &T::from(2))
+            && &(r.clone() * r) != n
+            && is_strong_lucas_probable_prime(n)
+    }
+}
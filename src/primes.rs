@@ -0,0 +1,224 @@
+use std::ops::Range;
+use std::sync::Mutex;
+
+use crate::integers::Integer;
+
+/// Residues modulo 30 coprime to 30, one per bit of a sieve byte.
+const WHEEL: [u64; 8] = [1, 7, 11, 13, 17, 19, 23, 29];
+
+/// Inverses modulo 30 of [`WHEEL`], in the same order.
+const WHEEL_INVERSES: [u64; 8] = [1, 13, 11, 7, 23, 19, 17, 29];
+
+/// Primes which the wheel skips over.
+const WHEEL_PRIMES: [u64; 3] = [2, 3, 5];
+
+/// Largest segment, in bytes of 30 integers each, which stays within a typical L1 cache.
+const SEGMENT_BYTES: u64 = 1 << 15;
+
+/// First segment of a [`Primes`] iterator, which doubles up to [`SEGMENT_BYTES`],
+/// so that taking just a few primes is cheap.
+const FIRST_SEGMENT_BYTES: u64 = 1 << 8;
+
+/// Number of wheel bytes covering every `u64`.
+const LAST_BYTE: u64 = u64::MAX / 30 + 1;
+
+/// Bound up to which [`sieving_primes`] sieves one integer per byte, rather than
+/// by segments (which need sieving primes of their own).
+const SMALL_SIEVE_BOUND: u64 = 1 << 16;
+
+/// Smallest bound to which [`cached`] sieves at a time.
+const CACHE_BOUND: u64 = 1 << 16;
+
+/// Primes sieved so far for [`cached`], in increasing order.
+static CACHE: Mutex<Vec<u64>> = Mutex::new(Vec::new());
+
+/// Primes from 7 up to `limit` (at most $\sqrt{2^{64}}$, so that they fit a `u32`),
+/// to sieve the segments with.
+///
+/// These are sieved by segments in turn, with the primes up to $\sqrt{limit}$,
+/// down to a small bound where a plain sieve is cheap.
+fn sieving_primes(limit: u64) -> Vec<u32> {
+    if limit > SMALL_SIEVE_BOUND {
+        let primes = segments(0, limit + 1, &sieving_primes(limit.sqrt()));
+        return primes.into_iter().map(|p| p as u32).collect();
+    }
+    let limit = limit as usize;
+    let mut composite = vec![false; limit + 1];
+    let mut primes = Vec::new();
+    for i in 2..=limit {
+        if composite[i] {
+            continue;
+        }
+        if i >= 7 {
+            primes.push(i as u32);
+        }
+        for j in (i * i..=limit).step_by(i) {
+            composite[j] = true;
+        }
+    }
+    primes
+}
+
+/// Primes from 7 in the `bytes` wheel bytes from `first`, which is $[30 f, 30 (f + b))$,
+/// in increasing order. `sieving` has to hold every prime from 7 whose square is in range.
+///
+/// Bit $j$ of byte $k$ stands for $30 (f + k) + w_j$, where $w_j$ is the $j$-th [`WHEEL`]
+/// residue. The multiples $pq$ of a sieving prime $p$ with $pq \equiv w_j \pmod{30}$ have
+/// $q \equiv w_j p^{-1}$, so they all sit at bit $j$, $p$ bytes apart, from $q \ge p$.
+fn segment(first: u64, bytes: u64, sieving: &[u32]) -> Vec<u64> {
+    let lo = 30 * first;
+    // the last byte runs past u64::MAX
+    let hi = 30u64.saturating_mul(first + bytes);
+    let mut sieve = vec![0xffu8; bytes as usize];
+    if first == 0 {
+        // 1 is not a prime
+        sieve[0] &= !1;
+    }
+    for &p in sieving.iter().take_while(|&&p| (p as u64).pow(2) < hi) {
+        let p = p as u64;
+        let inverse = WHEEL_INVERSES[WHEEL.iter().position(|&w| w == p % 30).unwrap()];
+        let q0 = p.max(lo.div_ceil(p));
+        for (j, &w) in WHEEL.iter().enumerate() {
+            let q = q0 + (w * inverse + 30 - q0 % 30) % 30;
+            // pq can be past u64::MAX in the last byte
+            let mut k = (p as u128 * q as u128 / 30) as u64 - first;
+            while k < bytes {
+                sieve[k as usize] &= !(1 << j);
+                k += p;
+            }
+        }
+    }
+    let mut primes = Vec::new();
+    for (k, &byte) in sieve.iter().enumerate() {
+        let mut bits = byte;
+        while bits != 0 {
+            let j = bits.trailing_zeros() as usize;
+            if let Some(p) = (lo + 30 * k as u64).checked_add(WHEEL[j]) {
+                primes.push(p);
+            }
+            bits &= bits - 1;
+        }
+    }
+    primes
+}
+
+/// Primes in `range`, in increasing order.
+///
+/// Sieves the range by segments of the 2·3·5 wheel, bit-packed to eight
+/// candidates per 30 integers, with the primes up to $\sqrt{hi}$.
+pub fn primes_in(range: Range<u64>) -> Vec<u64> {
+    let Range { start, end } = range;
+    let mut primes: Vec<u64> = WHEEL_PRIMES
+        .iter()
+        .cloned()
+        .filter(|p| (start..end).contains(p))
+        .collect();
+    if end <= start {
+        return primes;
+    }
+    primes.extend(segments(start, end, &sieving_primes(end.sqrt())));
+    primes
+}
+
+/// Primes from 7 in $[start, end)$, sieved by segments of at most [`SEGMENT_BYTES`]
+/// with the `sieving` primes (as for [`segment`]).
+fn segments(start: u64, end: u64, sieving: &[u32]) -> Vec<u64> {
+    let last = end.div_ceil(30);
+    let mut first = start / 30;
+    let mut primes = Vec::new();
+    while first < last {
+        let bytes = SEGMENT_BYTES.min(last - first);
+        let segment = segment(first, bytes, sieving);
+        primes.extend(segment.into_iter().filter(|p| (start..end).contains(p)));
+        first += bytes;
+    }
+    primes
+}
+
+/// The $n$-th prime, counting from $p_1 = 2$.
+///
+/// # Panics
+///
+/// If `n` is zero.
+pub fn nth_prime(n: usize) -> u64 {
+    assert!(n > 0, "primes are counted from 1");
+    primes_from(0).nth(n - 1).unwrap()
+}
+
+/// Primes from `x` on, in increasing order, without sieving anything below `x`.
+pub fn primes_from(x: u64) -> Primes {
+    Primes {
+        start: x,
+        next_byte: x / 30,
+        bytes: FIRST_SEGMENT_BYTES,
+        sieving: Vec::new(),
+        sieved_to: 0,
+        segment: WHEEL_PRIMES.to_vec().into_iter(),
+    }
+}
+
+/// Iterator over the primes from some bound on, see [`primes_from`], up to `u64::MAX`.
+///
+/// Sieves one segment at a time, and extends its sieving primes as it goes.
+pub struct Primes {
+    /// Primes below this are skipped
+    start: u64,
+    /// First wheel byte of the next segment
+    next_byte: u64,
+    /// Size of the next segment
+    bytes: u64,
+    /// Sieving primes up to `sieved_to`
+    sieving: Vec<u32>,
+    sieved_to: u64,
+    /// Primes of the current segment left to yield
+    segment: std::vec::IntoIter<u64>,
+}
+
+impl Default for Primes {
+    fn default() -> Primes {
+        primes_from(0)
+    }
+}
+
+impl Iterator for Primes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            if let Some(p) = self.segment.next() {
+                if p >= self.start {
+                    return Some(p);
+                }
+                continue;
+            }
+            if self.next_byte == LAST_BYTE {
+                return None;
+            }
+            self.bytes = self.bytes.min(LAST_BYTE - self.next_byte);
+            let limit = 30u64.saturating_mul(self.next_byte + self.bytes).sqrt();
+            if limit > self.sieved_to {
+                // at least double, so that it is rebuilt only logarithmically often
+                self.sieved_to = limit.max(2 * self.sieved_to).min(u32::MAX as u64);
+                self.sieving = sieving_primes(self.sieved_to);
+            }
+            self.segment = segment(self.next_byte, self.bytes, &self.sieving).into_iter();
+            self.next_byte += self.bytes;
+            self.bytes = SEGMENT_BYTES.min(2 * self.bytes);
+        }
+    }
+}
+
+/// The primes, in increasing order, from a shared cache which is sieved
+/// further (to twice its bound) whenever it runs out, so that the factor bases
+/// of repeated sieves share the work.
+pub(crate) fn cached() -> impl Iterator<Item = u64> {
+    (0..).map(|i| {
+        let mut cache = CACHE.lock().unwrap();
+        while cache.len() <= i {
+            let lo = cache.last().map_or(0, |&p| p + 1);
+            let hi = (2 * lo).max(CACHE_BOUND);
+            cache.extend(primes_in(lo..hi));
+        }
+        cache[i]
+    })
+}
@@ -1,45 +1,96 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 #![allow(unused_imports)]
-use itertools::Itertools;
-
-use crate::integers::Integer;
-use crate::matrix::Matrix;
-
-#[inline]
-fn is_prime(x: i32) -> bool {
-    (2..=(x as f32).sqrt().floor() as i32).all(|d| x % d != 0)
-}
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::convert::TryFrom;
+use std::{error, fmt};
 
-fn primes_from(x: i32) -> impl Iterator<Item = i32> {
-    (x..).filter(|&x| is_prime(x))
-}
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
-fn primes() -> impl Iterator<Item = i32> {
-    primes_from(2)
-}
+use crate::bit_matrix::BitMatrix;
+use crate::integers::{pow_mod_int, Integer, IntegerRing, GCD};
+use crate::jacobi::jacobi;
+use crate::lehman::lehman;
+use crate::perfect_power::is_perfect_power;
+use crate::primality::is_prime;
+use crate::primes::cached;
+use crate::rho::rho;
+use crate::squfof::squfof;
 
-fn legendre(n: i32, p: i32) -> i32 {
-    assert!(is_prime(p));
-    let e = ((p - 1) / 2) as u32;
-    n.pow_mod(e, p)
+/// The primes, from the shared cache of [`crate::primes`].
+pub(crate) fn primes() -> impl Iterator<Item = i32> {
+    cached().map(|p| p as i32)
 }
 
+/// Whether $n$ is a non-zero square modulo a prime $p$ (every $n$ counts for $p = 2$).
 #[inline]
 fn is_quadratic_residue(n: i32, p: i32) -> bool {
-    if n > 2 {
-        legendre(n, p) == 1
-    } else {
-        true
-    }
+    p == 2 || jacobi(&n, &p) == 1
 }
 
-fn legendre_primes(n: i32) -> impl Iterator<Item = i32> {
-    primes().filter(move |&p| is_quadratic_residue(n, p))
+/// Square-free multipliers $k$ considered by [`select_multiplier`].
+const MULTIPLIERS: [i32; 46] = [
+    1, 2, 3, 5, 6, 7, 10, 11, 13, 14, 15, 17, 19, 21, 22, 23, 26, 29, 30, 31, 33, 34, 35, 37, 38,
+    39, 41, 42, 43, 46, 47, 51, 53, 55, 57, 58, 59, 61, 62, 65, 66, 67, 69, 70, 71, 73,
+];
+
+/// Bound on the primes scored by [`select_multiplier`].
+const MULTIPLIER_PRIME_BOUND: i32 = 1000;
+
+/// Choose a multiplier $k$ for sieving $kn$ rather than $n$, with the Knuth-Schroeppel function.
+///
+/// Each $k$ is scored by the expected contribution of the small primes $p$ to the logarithm
+/// of a sieve value, $\sum_p g(p, kn) \log p - \frac12 \log k$ (the values are $\sqrt k$ times larger),
+/// where $g(p, kn)$ is $2/(p-1)$ if $kn$ is a non-zero square modulo $p$ and $1/p$ if $p$ divides $kn$.
+/// For $p = 2$ it depends on $kn$ modulo 8, as odd squares are all 1 modulo 8.
+pub fn select_multiplier<T: IntegerRing>(n: &T) -> i32 {
+    let score = |k: i32| {
+        let mut score = -0.5 * (k as f64).ln();
+        for p in primes().take_while(|&p| p < MULTIPLIER_PRIME_BOUND) {
+            let log_p = (p as f64).ln();
+            let kn_p = (n.rem_small(p as u32) as i32 * k) % p;
+            score += if kn_p == 0 {
+                log_p / p as f64
+            } else if p == 2 {
+                match (n.rem_small(8) as i32 * k) % 8 {
+                    1 => 2.0 * log_p,
+                    5 => log_p,
+                    _ => 0.5 * log_p,
+                }
+            } else if is_quadratic_residue(kn_p, p) {
+                2.0 * log_p / (p - 1) as f64
+            } else {
+                0.0
+            };
+        }
+        score
+    };
+    MULTIPLIERS
+        .iter()
+        .copied()
+        .map(|k| (k, score(k)))
+        .fold((1, f64::NEG_INFINITY), |best, (k, s)| {
+            if s > best.1 {
+                (k, s)
+            } else {
+                best
+            }
+        })
+        .0
 }
 
-fn factor_base(n: i32, b: usize) -> Vec<i32> {
-    legendre_primes(n).take(b).collect()
+/// $kn$ for the multiplier $k$ of [`select_multiplier`], if `T` has room for $2kn$
+/// (whose square root the polynomial sieves aim their $A$ at).
+///
+/// Whether the sieve values fit as well depends on the interval, and is checked by each sieve.
+fn multiplied<T: IntegerRing>(n: &T) -> Result<T, QsError> {
+    let k = select_multiplier(n);
+    // kn has at most as many bits as n and k together
+    if n.bit_length() + k.bit_length() + 1 > T::BITS {
+        return Err(QsError::TooLarge);
+    }
+    Ok(T::from(k) * n.clone())
 }
 
 struct Squaring {
@@ -57,7 +108,7 @@ impl Iterator for Squaring {
     type Item = i32;
 
     fn next(&mut self) -> Option<i32> {
-        self.a = (self.a * self.a) % self.m;
+        self.a = self.a.mul_mod(&self.a, &self.m);
         Some(self.a)
     }
 }
@@ -87,6 +138,9 @@ fn sqrt_mod(n: i32, p: i32) -> Option<[i32; 2]> {
         let r = n % 2;
         return Some([r, r]);
     }
+    if n % p == 0 {
+        return Some([0, 0]);
+    }
     if !is_quadratic_residue(n, p) {
         return None;
     }
@@ -114,78 +168,1145 @@ fn sqrt_mod(n: i32, p: i32) -> Option<[i32; 2]> {
             .next()
             .unwrap();
         let b = c.pow_mod((1 << (m - i - 1)) as u32, p);
-        r = (r * b) % p;
-        c = (b * b) % p;
-        t = (t * c) % p;
+        r = r.mul_mod(&b, &p);
+        c = b.mul_mod(&b, &p);
+        t = t.mul_mod(&c, &p);
         m = i;
     }
     Some([r, p - r])
 }
 
-fn smooth(n: i32, b: usize, i: usize) -> (Vec<i32>, Vec<i32>, Vec<i32>) {
-    let m = (n as f32).sqrt().ceil() as i32;
-    let mut sieve: Vec<i32> = (0..i).map(|x| (x as i32 + m).squared() - n).collect();
-    // Collect primes p for which n has roots mod p, as well as collec the roots
-    let (fb, roots): (Vec<i32>, Vec<[i32; 2]>) = primes()
-        .filter_map(|p| sqrt_mod(n, p).map(|r| (p, r)))
+/// Smallest integer whose square is at least `n`.
+fn ceil_sqrt<T: IntegerRing>(n: &T) -> T {
+    let m = n.sqrt();
+    if &(m.clone() * m.clone()) < n {
+        m + T::ONE
+    } else {
+        m
+    }
+}
+
+/// A relation $x^2 \equiv a^2 y \pmod n$, where $y$ is smooth over the factor base,
+/// kept as the non-zero exponents of its sign and of the factor base primes in its
+/// factorization, by their index in the factor base (as most primes don't divide $y$).
+struct Relation<T> {
+    x: T,
+    exponents: Vec<(usize, u32)>,
+    a: T,
+}
+
+/// Factor base primes, the first `b` primes $p$ for which $n$ has roots modulo $p$
+/// (including those dividing $n$), alongside the roots.
+///
+/// The sieves' factor base is -1 (for the sign) followed by these, see [`Relations::new`].
+fn factor_base_roots<T: IntegerRing>(n: &T, b: usize) -> (Vec<i32>, Vec<[i32; 2]>) {
+    primes()
+        .filter_map(|p| sqrt_mod(n.rem_small(p as u32) as i32, p).map(|r| (p, r)))
         .take(b)
-        .unzip();
-    for (p, p_roots) in fb.iter().zip(roots.iter()) {
-        for r in p_roots {
-            let start = (r - m).rem_euclid(*p) as usize;
-            for i in (start..sieve.len()).step_by(*p as usize) {
-                //println!("{} {} {} {}", p, r, i, sieve[i]);
-                while sieve[i] % p == 0 {
-                    sieve[i] /= p;
-                    //println!("reduce {} {} {} {}", p, r, i, sieve[i]);
+        .unzip()
+}
+
+/// Sieve threshold for values up to `max` in magnitude: positions whose summed
+/// logarithms reach it are candidates for trial factoring.
+///
+/// The fudge factor `t` (typically between 1 and 3) allows for the prime powers and
+/// small primes which the sieve doesn't account for, where larger `t` means more candidates.
+fn threshold<T: IntegerRing>(max: &T, p_max: i32, t: f64) -> u8 {
+    let bits = max.bit_length() as f64;
+    (bits - t * (p_max as f64).log2()).clamp(0.0, 255.0) as u8
+}
+
+/// Length of the blocks the sieve interval is processed in, sized to fit in L1 cache.
+const BLOCK_SIZE: usize = 1 << 15;
+
+/// Sieve a polynomial over an interval of length `len`, given the starting index
+/// for each root of each factor base prime, returning the indices of the candidates:
+/// those where the approximate logarithms (base 2) of the primes hitting it reach the threshold.
+///
+/// The interval is sieved one block of [`BLOCK_SIZE`] at a time, carrying the next
+/// index of each root over to the following block, so memory use doesn't grow with `len`.
+fn sieve(len: usize, fb: &[i32], starts: &[Vec<usize>], threshold: u8) -> Vec<usize> {
+    let logs_p: Vec<u8> = fb
+        .iter()
+        .map(|&p| (p as f64).log2().round() as u8)
+        .collect();
+    // Next index to sieve for each root, relative to the current block,
+    // where a double root (as modulo 2) is only sieved once
+    let mut next: Vec<Vec<usize>> = starts
+        .iter()
+        .map(|p_starts| p_starts.iter().copied().unique().collect())
+        .collect();
+    let mut block = [0u8; BLOCK_SIZE];
+    let mut candidates = vec![];
+    for lo in (0..len).step_by(BLOCK_SIZE) {
+        let size = BLOCK_SIZE.min(len - lo);
+        let logs = &mut block[..size];
+        logs.fill(0);
+        for ((&p, &log_p), p_next) in fb.iter().zip(logs_p.iter()).zip(next.iter_mut()) {
+            for j in p_next.iter_mut() {
+                while *j < size {
+                    logs[*j] = logs[*j].saturating_add(log_p);
+                    *j += p as usize;
                 }
+                *j -= size;
             }
         }
+        candidates.extend(
+            logs.iter()
+                .enumerate()
+                .filter_map(|(i, &l)| (l >= threshold).then_some(lo + i)),
+        );
     }
-    let xs: Vec<i32> = sieve
-        .into_iter()
-        .enumerate()
-        .filter_map(|(i, v)| (v.abs() == 1).then_some(i as i32 + m))
+    candidates
+}
+
+/// Sieve $x^2 - n$ over an interval of length `i` centred on $\sqrt n$,
+/// where the values below $\sqrt n$ are negative.
+fn smooth<T: IntegerRing>(n: &T, b: usize, i: usize, t: f64) -> Relations<T> {
+    let m = ceil_sqrt(n) - T::from((i / 2) as i32);
+    // Collect primes p for which n has roots mod p, as well as collec the roots
+    let (fb, roots) = factor_base_roots(n, b);
+    let starts: Vec<Vec<usize>> = fb
+        .iter()
+        .zip(roots.iter())
+        .map(|(&p, p_roots)| {
+            let m_p = m.rem_small(p as u32) as i32;
+            p_roots
+                .iter()
+                .map(|r| (r - m_p).rem_euclid(p) as usize)
+                .collect()
+        })
         .collect();
-    let ys: Vec<i32> = xs.iter().map(|x| x.squared() - n).collect();
-    (xs, ys, fb)
+    let ends = [m.clone(), T::from(i as i32) + m.clone()];
+    let max = ends
+        .iter()
+        .map(|x| (x.squared() - n.clone()).abs())
+        .max()
+        .unwrap();
+    let threshold = threshold(&max, *fb.last().unwrap_or(&2), t);
+    let mut relations = Relations::new(n, &fb, LARGE_PRIME_MULTIPLIER);
+    for i in sieve(i, &fb, &starts, threshold) {
+        let x = T::from(i as i32) + m.clone();
+        let y = x.squared() - n.clone();
+        relations.add(x, y, T::ONE);
+    }
+    relations
+}
+
+/// Polynomial $Q(x) = ((Ax + B)^2 - n) / A = Ax^2 + 2Bx + C$ for the multiple polynomial sieves,
+/// where $B^2 \equiv n \pmod A$ (so that $(Ax + B)^2 \equiv A Q(x) \pmod n$).
+struct Polynomial<T> {
+    a: T,
+    b: T,
+    c: T,
 }
 
-fn factor_over(n: &i32, fb: &Vec<i32>) -> (i32, Vec<(i32, u32)>) {
+impl<T: IntegerRing> Polynomial<T> {
+    fn eval(&self, x: i32) -> T {
+        let x = T::from(x);
+        (self.a.clone() * x.clone() + self.b.clone() * T::from(2)) * x + self.c.clone()
+    }
+
+    /// Bound on $|Q(x)|$ over $[-M, M]$.
+    fn max_abs(&self, m: i32) -> T {
+        let (l, c, r) = (self.eval(-m).abs(), self.eval(0).abs(), self.eval(m).abs());
+        l.max(c).max(r)
+    }
+
+    /// The square root $Ax + B$ of $A Q(x) + n$.
+    fn root(&self, x: i32) -> T {
+        self.a.clone() * T::from(x) + self.b.clone()
+    }
+}
+
+/// Factor by which the $q$ of [`Polynomials`] may stray from $\sqrt A$ for the ideal $A$,
+/// which keeps $A$, and about the sieve values, within a factor of its square of the ideal.
+const MAX_DRIFT: i32 = 4;
+
+/// Whether `T` has room for the values $A Q(x) = (Ax + B)^2 - n$ of a polynomial
+/// over $[-M, M]$, and for the products in computing them: $B^2$ for $C$,
+/// and $(Ax + 2B) x$ in [`Polynomial::eval`].
+fn polynomial_fits<T: IntegerRing>(a: &T, b: &T, m: i32) -> bool {
+    let m = T::from(m);
+    if a.bit_length() + m.bit_length() + 1 > T::BITS || b.bit_length() + 2 > T::BITS {
+        return false;
+    }
+    // |Ax + B| and |Ax + 2B|, and so |x| too, are at most AM + 2|B|
+    let root = a.clone() * m + b.abs() * T::from(2);
+    2 * root.bit_length() < T::BITS
+}
+
+/// Iterator over MPQS polynomials, with $A = q^2$ for primes $q \equiv 3 \pmod 4$
+/// for which $n$ is a quadratic residue (yielding $q$ alongside the polynomial).
+///
+/// The $q$ are taken alternately above and below $\sqrt{A}$ for the ideal
+/// $A \approx \sqrt{2n}/M$ (for which $Q$ is smallest over $[-M, M]$), moving away from it,
+/// until they are more than [`MAX_DRIFT`] times off on both sides.
+/// A polynomial whose values don't fit `T` gives [`QsError::TooLarge`].
+struct Polynomials<'a, T> {
+    n: &'a T,
+    m: i32,
+    /// Next candidates above and below, both $3 \bmod 4$
+    up: T,
+    down: T,
+    /// Range of the candidates
+    lo: T,
+    hi: T,
+    /// Whether the next candidate is taken from above
+    next_up: bool,
+}
+
+impl<'a, T: IntegerRing> Polynomials<'a, T> {
+    fn new(n: &'a T, m: usize) -> Self {
+        let root = ((n.clone() * T::from(2)).sqrt() / T::from(m as i32)).sqrt();
+        let mut up = root.clone().max(T::from(3));
+        // start at the first q = 3 mod 4, from which we step by 4
+        while up.rem_small(4) != 3 {
+            up = up + T::ONE;
+        }
+        Self {
+            n,
+            m: m as i32,
+            down: up.clone() - T::from(4),
+            up,
+            lo: (root.clone() / T::from(MAX_DRIFT)).max(T::from(3)),
+            hi: (root * T::from(MAX_DRIFT)).max(T::from(3)),
+            next_up: true,
+        }
+    }
+
+    /// The next candidate $q$, if any are left in range.
+    fn next_q(&mut self) -> Option<T> {
+        let four = T::from(4);
+        let up_left = self.up <= self.hi;
+        let down_left = self.down >= self.lo;
+        let take_up = up_left && (self.next_up || !down_left);
+        self.next_up = !self.next_up;
+        if take_up {
+            let q = self.up.clone();
+            self.up = q.clone() + four;
+            Some(q)
+        } else if down_left {
+            let q = self.down.clone();
+            self.down = q.clone() - four;
+            Some(q)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T: IntegerRing> Iterator for Polynomials<'a, T> {
+    type Item = Result<(T, Polynomial<T>), QsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.n;
+        let (one, two, four) = (T::ONE, T::from(2), T::from(4));
+        loop {
+            let q = self.next_q()?;
+            // Euler's criterion for n being a quadratic residue modulo q
+            let e = (q.clone() - one.clone()) / two.clone();
+            if pow_mod_int(n, &e, &q) != one {
+                continue;
+            }
+            // Since q = 3 mod 4, n^((q+1)/4) is a root of n modulo q,
+            // which we Hensel lift to a root B = b + kq modulo q^2.
+            let b = pow_mod_int(n, &((q.clone() + one.clone()) / four.clone()), &q);
+            let r = n.clone() - b.clone() * b.clone();
+            if r.clone() % q.clone() != T::ZERO {
+                continue; // q is not prime
+            }
+            let inv = pow_mod_int(&(b.clone() * two.clone()), &(q.clone() - two.clone()), &q);
+            let k = (r / q.clone()).mul_mod(&inv, &q);
+            let b = b + k * q.clone();
+            let a = q.clone() * q.clone();
+            if !polynomial_fits(&a, &b, self.m) {
+                return Some(Err(QsError::TooLarge));
+            }
+            let r = b.clone() * b.clone() - n.clone();
+            if r.clone() % a.clone() != T::ZERO {
+                continue; // q is not prime
+            }
+            let c = r / a.clone();
+            return Some(Ok((q, Polynomial { a, b, c })));
+        }
+    }
+}
+
+/// Trial divide `n` over the factor base `fb`, returning the part of $|n|$ left over,
+/// alongside the indices in `fb` of the primes (or -1) which divide $n$, with their exponents.
+pub(crate) fn factor_over<T: IntegerRing>(n: &T, fb: &[i32]) -> (T, Vec<(usize, u32)>) {
     let mut n = n.clone();
     let mut factorization = Vec::new();
-    for p in fb.iter() {
+    for (j, &p) in fb.iter().enumerate() {
+        if p == -1 {
+            if n < T::ZERO {
+                n = -n;
+                factorization.push((j, 1));
+            }
+            continue;
+        }
         let mut i = 0;
-        while n % p == 0 {
-            n /= p;
+        while n.rem_small(p as u32) == 0 && n != T::ZERO {
+            n = n / T::from(p);
             i += 1;
         }
-        factorization.push((*p, i));
+        if i > 0 {
+            factorization.push((j, i));
+        }
     }
     (n, factorization)
 }
 
-fn construct_exponent_matrices<const B: usize>(_ys: &Vec<i32>, _fb: &Vec<i32>)
-where
-    [(); B + 1]: Sized,
-{
-    let a: Matrix<i32, { B + 1 }, B>;
-    // NOTE: OOOOOPS. m and n are non-constant
-    // Matrix::<i32, m, n>::zero();
+/// Multiplier $k$ for the large prime bound $k p_{max}$, below which the cofactors
+/// of partial relations are kept.
+pub(crate) const LARGE_PRIME_MULTIPLIER: i32 = 64;
+
+/// Bound on the steps of Pollard's rho method in splitting a cofactor
+/// too wide for [`squfof`] into two large primes, which are below the large prime bound.
+const COFACTOR_RHO_ITERATIONS: usize = 1 << 12;
+
+/// A non-negative `c` as a machine word, if it fits in the 62 bits which [`squfof`]
+/// and [`lehman`] take, read 16 bits at a time (so that the base fits any `T`).
+fn to_u64<T: IntegerRing>(c: &T) -> Option<u64> {
+    let base = T::from(1 << 16);
+    let mut c = c.clone();
+    let mut x = 0;
+    for shift in (0..64).step_by(16) {
+        x |= (c.rem_small(1 << 16) as u64) << shift;
+        c = c / base.clone();
+    }
+    (c == T::ZERO && x < 1 << 62).then_some(x)
 }
 
+/// Split the cofactor `c` of a partial relation into large primes below `bound`,
+/// with 1 for the first if there is just one.
+///
+/// The cofactor of a single large prime relation is not checked to be prime,
+/// since combining relations only needs the cofactors to match.
+pub fn large_primes<T: IntegerRing>(c: T, bound: &T) -> Option<[T; 2]> {
+    if &c < bound {
+        return Some([T::ONE, c]);
+    }
+    if c >= bound.clone() * bound.clone() {
+        return None;
+    }
+    // a probable prime above the bound is of no use
+    if pow_mod_int(&T::from(2), &(c.clone() - T::ONE), &c) == T::ONE {
+        return None;
+    }
+    let p = match to_u64(&c) {
+        Some(c) => {
+            let d = squfof(c).or_else(|| lehman(c))?;
+            // the smaller factor is below the bound whenever the split is of use
+            T::from(i32::try_from(d.min(c / d)).ok()?)
+        }
+        None => rho(&c, COFACTOR_RHO_ITERATIONS)?,
+    };
+    let q = c.clone() / p.clone();
+    if p.clone() * q.clone() != c {
+        return None;
+    }
+    if &p < bound && &q < bound {
+        Some([p.clone().min(q.clone()), p.max(q)])
+    } else {
+        None
+    }
+}
+
+/// Relations collected while sieving, including partial relations, where $y$ factors
+/// over the factor base up to one or two large primes (below the large prime bound).
+///
+/// Partial relations are the edges of a graph on the large primes and 1
+/// (the other end of those with a single large prime).
+/// Each cycle in it gives a full relation as the product of its edges,
+/// where every large prime on the cycle appears squared (and so moves into $a$).
+/// While sieving the independent cycles are only counted, with union-find,
+/// and they are combined once the relations are used.
+pub(crate) struct Relations<T> {
+    n: T,
+    /// Factor base of -1 followed by the sieving primes
+    fb: Vec<i32>,
+    large_prime_bound: T,
+    full: Vec<Relation<T>>,
+    partial: Vec<(Relation<T>, [T; 2])>,
+    /// Union-find parents of the vertices of the partial relation graph (roots are missing)
+    parent: BTreeMap<T, T>,
+    /// Number of independent cycles in the partial relation graph
+    cycles: usize,
+    /// Number of values $y$ trial divided over the factor base
+    candidates: usize,
+}
+
+impl<T: IntegerRing> Relations<T> {
+    pub(crate) fn new(n: &T, primes: &[i32], large_prime_multiplier: i32) -> Self {
+        let p_max = primes.last().copied().unwrap_or(2);
+        Self {
+            n: n.clone(),
+            fb: std::iter::once(-1).chain(primes.iter().copied()).collect(),
+            large_prime_bound: T::from(p_max) * T::from(large_prime_multiplier),
+            full: Vec::new(),
+            partial: Vec::new(),
+            parent: BTreeMap::new(),
+            cycles: 0,
+            candidates: 0,
+        }
+    }
+
+    /// Extend the factor base to `primes`, which have to start with the current sieving
+    /// primes, so that the relations collected so far keep their exponent indices.
+    fn extend_factor_base(&mut self, primes: &[i32], large_prime_multiplier: i32) {
+        debug_assert!(self.fb[1..] == primes[..self.fb.len() - 1]);
+        let p_max = primes.last().copied().unwrap_or(2);
+        self.fb.extend_from_slice(&primes[self.fb.len() - 1..]);
+        self.large_prime_bound = T::from(p_max) * T::from(large_prime_multiplier);
+    }
+
+    /// Number of full relations, including those combined from partial relations.
+    pub(crate) fn len(&self) -> usize {
+        self.full.len() + self.cycles
+    }
+
+    pub(crate) fn stats(&self) -> Stats {
+        Stats {
+            factor_base: self.fb.len(),
+            candidates: self.candidates,
+            full: self.full.len(),
+            partial: self.partial.len(),
+            cycles: self.cycles,
+        }
+    }
+
+    /// Add the relation $x^2 \equiv a^2 y \pmod n$ if $y$ is smooth
+    /// or has one or two large primes, and discard it otherwise.
+    pub(crate) fn add(&mut self, x: T, y: T, a: T) {
+        self.candidates += 1;
+        let (rem, exponents) = factor_over(&y, &self.fb);
+        let relation = Relation { x, exponents, a };
+        if rem == T::ONE {
+            self.full.push(relation);
+        } else if let Some(large_primes) = large_primes(rem, &self.large_prime_bound) {
+            self.add_partial(relation, large_primes);
+        }
+    }
+
+    /// Root of the union-find tree containing `v`, compressing the path to it.
+    fn find(&mut self, v: &T) -> T {
+        let mut root = v.clone();
+        let mut path = Vec::new();
+        while let Some(p) = self.parent.get(&root) {
+            path.push(root);
+            root = p.clone();
+        }
+        for u in path {
+            self.parent.insert(u, root.clone());
+        }
+        root
+    }
+
+    fn add_partial(&mut self, relation: Relation<T>, [p, q]: [T; 2]) {
+        self.partial.push((relation, [p.clone(), q.clone()]));
+        let (root_p, root_q) = (self.find(&p), self.find(&q));
+        if root_p == root_q {
+            // p and q are already connected, so this edge closes a cycle
+            self.cycles += 1;
+        } else {
+            self.parent.insert(root_p, root_q);
+        }
+    }
+
+    /// Full relations combined from the cycles of the partial relation graph.
+    ///
+    /// Each edge outside of a breadth-first spanning forest closes a cycle with
+    /// the forest's path between its ends, found by climbing from both to their common ancestor.
+    fn combine_cycles(&self) -> Vec<Relation<T>> {
+        let mut adjacent: BTreeMap<&T, Vec<(&T, usize)>> = BTreeMap::new();
+        for (i, (_, [p, q])) in self.partial.iter().enumerate() {
+            adjacent.entry(p).or_default().push((q, i));
+            adjacent.entry(q).or_default().push((p, i));
+        }
+        // depth of each vertex in the spanning forest, and its parent (with the edge to it)
+        let mut depth: BTreeMap<&T, usize> = BTreeMap::new();
+        let mut parent: BTreeMap<&T, (&T, usize)> = BTreeMap::new();
+        let mut in_tree = vec![false; self.partial.len()];
+        for &root in adjacent.keys() {
+            if depth.contains_key(root) {
+                continue;
+            }
+            depth.insert(root, 0);
+            let mut queue = VecDeque::from(vec![root]);
+            while let Some(u) = queue.pop_front() {
+                let d = depth[u];
+                for &(v, i) in &adjacent[u] {
+                    if !depth.contains_key(v) {
+                        depth.insert(v, d + 1);
+                        parent.insert(v, (u, i));
+                        in_tree[i] = true;
+                        queue.push_back(v);
+                    }
+                }
+            }
+        }
+        (0..self.partial.len())
+            .filter(|&i| !in_tree[i])
+            .map(|i| {
+                let [p, q] = &self.partial[i].1;
+                let (mut u, mut v) = (p, q);
+                let mut cycle = vec![i];
+                while u != v {
+                    // climb from the deeper of the two
+                    if depth[u] < depth[v] {
+                        std::mem::swap(&mut u, &mut v);
+                    }
+                    let (w, j) = parent[u];
+                    cycle.push(j);
+                    u = w;
+                }
+                self.combine(&cycle)
+            })
+            .collect()
+    }
+
+    /// Full relation from the product of the partial relations on a cycle.
+    fn combine(&self, cycle: &[usize]) -> Relation<T> {
+        let n = &self.n;
+        let mut x = T::ONE;
+        let mut a = T::ONE;
+        let mut exponents = BTreeMap::new();
+        let mut large_primes = Vec::new();
+        for &i in cycle {
+            let (relation, primes) = &self.partial[i];
+            x = x.mul_mod(&relation.x, n);
+            a = a.mul_mod(&relation.a, n);
+            for &(j, e) in &relation.exponents {
+                *exponents.entry(j).or_insert(0) += e;
+            }
+            large_primes.extend(primes.iter().cloned());
+        }
+        // each large prime on the cycle is at the ends of two of its edges
+        large_primes.sort();
+        large_primes.dedup();
+        let a = large_primes.iter().fold(a, |a, l| a.mul_mod(l, n));
+        let exponents = exponents.into_iter().collect();
+        Relation { x, exponents, a }
+    }
+}
+
+/// Construct the exponent matrix modulo 2, where the i'th row consists of the parities
+/// of the exponents of the factor base primes for the factorization of the i'th relation's $y$.
+///
+/// The first column is for the factor base's -1, the sign of the smooth numbers
+/// (with exponent 1 for negative numbers), and there are `n` columns in all.
+fn construct_exponent_matrix<T>(relations: &[&Relation<T>], n: usize) -> BitMatrix {
+    let mut bmat = BitMatrix::zeroes(relations.len(), n);
+    for (i, r) in relations.iter().enumerate() {
+        for &(j, e) in &r.exponents {
+            bmat.set(i, j, e % 2 == 1);
+        }
+    }
+    bmat
+}
+
+/// Combine relations into a congruence of squares $x^2 \equiv y^2 \pmod{kn}$,
+/// trying each dependency until one gives a non-trivial factor pair of $n$.
+pub(crate) fn find_factor<T: IntegerRing>(
+    n: &T,
+    relations: &Relations<T>,
+) -> Result<(T, T), QsError> {
+    let (kn, fb) = (&relations.n, &relations.fb);
+    let combined = relations.combine_cycles();
+    let relations: Vec<&Relation<T>> = relations.full.iter().chain(combined.iter()).collect();
+    let bmat = construct_exponent_matrix(&relations, fb.len());
+    // The augmented binary matrix is for solving for the left nullspace,
+    // and thus all the subsets of linearly dependent exponent vector indices.
+    let mut dependencies = 0;
+    for v in bmat.iter_lnull_span() {
+        dependencies += 1;
+        let indices: Vec<usize> = (0..v.len()).filter(|&i| v[i]).collect();
+        // x is the product of the x's, and y the square root of the product of the a^2 y's,
+        // (which we know from halving the summed exponent vectors), so that x^2 = y^2 mod n.
+        // The product of the y's is positive, so -1's exponent is even too.
+        let x = indices
+            .iter()
+            .fold(T::ONE, |x, &i| x.mul_mod(&relations[i].x, kn));
+        let y = indices
+            .iter()
+            .fold(T::ONE, |y, &i| y.mul_mod(&relations[i].a, kn));
+        let mut exponents = BTreeMap::new();
+        for &i in &indices {
+            for &(j, e) in &relations[i].exponents {
+                *exponents.entry(j).or_insert(0) += e;
+            }
+        }
+        let y = exponents.into_iter().fold(y, |y, (j, e)| {
+            y.mul_mod(&T::from(fb[j]).pow_mod(e / 2, kn.clone()), kn)
+        });
+        // k is divided out by taking the gcd with n rather than kn
+        let d = (x - y).abs().gcd(n.clone());
+        if d != T::ONE && &d != n {
+            return Ok((d.clone(), n.clone() / d));
+        }
+    }
+    Err(QsError::TrivialDependencies { dependencies })
+}
+
+/// Reasons the sieve can fail to factor $n$.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QsError {
+    /// $n < 2$, so 0, 1 or negative (for which $|n|$ can be factored instead)
+    TooSmall,
+    /// $kn$, for the multiplier $k$ of [`select_multiplier`], or the sieve values
+    /// would overflow the integer type
+    TooLarge,
+    /// $n$ is even, and so has the factor 2 (or is 2)
+    Even,
+    /// $n$ is (probably) prime, and so has no non-trivial factors
+    Prime,
+    /// $n$ is a perfect power $r^k$, which the sieve can't be relied on to split
+    /// (a square gives only trivial congruences of squares), but its root can be
+    PerfectPower,
+    /// The factor base has fewer primes than the sieve needs
+    FactorBaseTooSmall { found: usize, needed: usize },
+    /// Sieving gave fewer relations than needed for a dependency
+    NotEnoughRelations { found: usize, needed: usize },
+    /// Every dependency gave a congruence of squares with a trivial factor
+    TrivialDependencies { dependencies: usize },
+}
+
+impl fmt::Display for QsError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QsError::TooSmall => write!(fmt, "n is less than 2"),
+            QsError::TooLarge => write!(fmt, "n is too large for the integer type"),
+            QsError::Even => write!(fmt, "n is even"),
+            QsError::Prime => write!(fmt, "n is prime"),
+            QsError::PerfectPower => write!(fmt, "n is a perfect power"),
+            QsError::FactorBaseTooSmall { found, needed } => write!(
+                fmt,
+                "factor base too small: found {} primes, needed {}",
+                found, needed
+            ),
+            QsError::NotEnoughRelations { found, needed } => write!(
+                fmt,
+                "not enough relations: found {}, needed {}",
+                found, needed
+            ),
+            QsError::TrivialDependencies { dependencies } => write!(
+                fmt,
+                "all {} dependencies gave trivial factors",
+                dependencies
+            ),
+        }
+    }
+}
+
+impl error::Error for QsError {}
+
+/// Statistics of the relation collection of a run of [`qs`], [`siqs`] or [`crate::dixon::dixon`],
+/// for comparing them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Size of the factor base, including -1
+    pub factor_base: usize,
+    /// Values $y$ trial divided over the factor base
+    pub candidates: usize,
+    /// Relations with $y$ smooth over the factor base
+    pub full: usize,
+    /// Relations with one or two large primes
+    pub partial: usize,
+    /// Full relations combined from cycles of partial relations
+    pub cycles: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            fmt,
+            "factor base of {}, {} candidates: {} full and {} partial relations, {} cycles",
+            self.factor_base, self.candidates, self.full, self.partial, self.cycles
+        )
+    }
+}
+
+/// Check that $n$ is something the sieve can factor: above 1, odd, composite and
+/// not a perfect power.
+pub(crate) fn check<T: IntegerRing>(n: &T) -> Result<(), QsError> {
+    if n < &T::from(2) {
+        return Err(QsError::TooSmall);
+    }
+    if n.rem_small(2) == 0 {
+        return Err(QsError::Even);
+    }
+    if is_prime(n) {
+        return Err(QsError::Prime);
+    }
+    if is_perfect_power(n) {
+        return Err(QsError::PerfectPower);
+    }
+    Ok(())
+}
+
+/// Sieve threshold fudge factor that works well in general.
+pub const DEFAULT_FUDGE: f64 = 2.0;
+
 /// Quadratic sieve
 ///
 /// * `n` - Integer to factor
 /// * `b` - Desired factor base length
-/// * `i` - Sieving interval
-pub fn qs(n: i32, b: usize, i: usize) -> Result<(i32, i32), String> {
-    let (xs, ys, fb) = smooth(n, b, i);
-    if xs.len() <= fb.len() {
-        return Err("err".to_string());
+/// * `i` - Sieving interval length, centred on $\sqrt n$
+/// * `t` - Sieve threshold fudge factor (see [`DEFAULT_FUDGE`])
+///
+/// Generic over the integer type (e.g. `i64` or [`crate::bigint::I256`]),
+/// which needs room for $2kn$, for the multiplier $k \le 73$ of [`select_multiplier`],
+/// and for the squares $x^2$ over the interval, with $|x|$ up to $\sqrt{kn} + i/2$
+/// (products modulo $n$ are taken with [`Integer::mul_mod`], and don't need more),
+/// or else [`QsError::TooLarge`]. The same goes for [`mpqs`] and [`siqs`],
+/// whose values $(Ax + B)^2 - kn$ are checked for each polynomial.
+///
+/// Returns a non-trivial factor pair `(d, n / d)`.
+pub fn qs<T: IntegerRing>(n: T, b: usize, i: usize, t: f64) -> Result<(T, T), QsError> {
+    qs_with_stats(n, b, i, t).0
+}
+
+/// [`qs`], alongside the [`Stats`] of its relation collection
+/// (all zero if it doesn't get that far).
+pub fn qs_with_stats<T: IntegerRing>(
+    n: T,
+    b: usize,
+    i: usize,
+    t: f64,
+) -> (Result<(T, T), QsError>, Stats) {
+    let kn = match check(&n).and_then(|()| multiplied(&n)) {
+        Ok(kn) => kn,
+        Err(e) => return (Err(e), Stats::default()),
+    };
+    // the x sieved over are at most sqrt(kn) + i/2 (and one for rounding) in magnitude
+    let x_max = kn.sqrt() + T::from((i / 2) as i32 + 2);
+    if 2 * x_max.bit_length() > T::BITS {
+        return (Err(QsError::TooLarge), Stats::default());
+    }
+    let relations = smooth(&kn, b, i, t);
+    // one more relation than there are columns (-1 and the primes) makes a dependency
+    let needed = relations.fb.len() + 1;
+    let res = if relations.len() < needed {
+        Err(QsError::NotEnoughRelations {
+            found: relations.len(),
+            needed,
+        })
+    } else {
+        find_factor(&n, &relations)
+    };
+    (res, relations.stats())
+}
+
+/// Number of relations to collect beyond the number of exponent matrix columns,
+/// each of which gives another dependency to try.
+pub(crate) const EXTRA_RELATIONS: usize = 10;
+
+/// Number of polynomials after which the multiple polynomial sieve gives up.
+const MAX_POLYNOMIALS: usize = 10_000;
+
+/// Multiple polynomial quadratic sieve
+///
+/// Sieves polynomials $Q(x) = ((Ax + B)^2 - n) / A$ over $[-M, M]$,
+/// with $A \approx \sqrt{2n}/M$ the square of a prime,
+/// until there are more relations than factor base primes.
+///
+/// * `n` - Integer to factor
+/// * `b` - Desired factor base length
+/// * `m` - Sieving interval half-width $M$
+/// * `t` - Sieve threshold fudge factor (see [`DEFAULT_FUDGE`])
+///
+/// Returns a non-trivial factor pair `(d, n / d)`.
+pub fn mpqs<T: IntegerRing>(n: T, b: usize, m: usize, t: f64) -> Result<(T, T), QsError> {
+    check(&n)?;
+    let kn = multiplied(&n)?;
+    let (fb, roots) = factor_base_roots(&kn, b);
+    let p_max = fb.last().copied().unwrap_or(2);
+    let needed = fb.len() + 1 + EXTRA_RELATIONS;
+    let mut relations = Relations::new(&kn, &fb, LARGE_PRIME_MULTIPLIER);
+    for polynomial in Polynomials::new(&kn, m).take(MAX_POLYNOMIALS) {
+        let (q, poly) = polynomial?;
+        // Roots of Q modulo p are x = A^-1 (r - B) for the roots r of n modulo p
+        let starts: Vec<Vec<usize>> = fb
+            .iter()
+            .zip(roots.iter())
+            .map(|(&p, p_roots)| {
+                let a_p = poly.a.rem_small(p as u32) as i32;
+                if a_p == 0 {
+                    return Vec::new();
+                }
+                let a_inv = a_p.pow_mod((p - 2) as u32, p);
+                let b_p = poly.b.rem_small(p as u32) as i32;
+                let m_p = (m % p as usize) as i32;
+                p_roots
+                    .iter()
+                    .map(|r| (a_inv.mul_mod(&(r - b_p), &p) + m_p).rem_euclid(p) as usize)
+                    .collect()
+            })
+            .collect();
+        let m = m as i32;
+        let threshold = threshold(&poly.max_abs(m), p_max, t);
+        for i in sieve(2 * m as usize, &fb, &starts, threshold) {
+            let x = i as i32 - m;
+            // A = q^2 so that (Ax + B)^2 = q^2 Q(x) mod n
+            relations.add(poly.root(x), poly.eval(x), q.clone());
+        }
+        if relations.len() >= needed {
+            return find_factor(&n, &relations);
+        }
+    }
+    Err(QsError::NotEnoughRelations {
+        found: relations.len(),
+        needed,
+    })
+}
+
+/// The $2^{s-1}$ self-initializing polynomials for a given $A = q_1 \cdots q_s$
+/// (a product of factor base primes), with $B = \pm B_1 \pm \cdots \pm B_s$
+/// where $B_l^2 \equiv n \pmod{q_l}$ and $B_l \equiv 0 \pmod{q_k}$ for $k \ne l$.
+///
+/// The values of $B$ are enumerated in Gray code order, so that switching polynomial
+/// changes a single $B_l$, and each root of $Q$ modulo $p$ moves by $\pm 2 B_l A^{-1}$.
+struct SelfInitializing<T> {
+    a: T,
+    b: T,
+    bs: Vec<T>,
+    /// $2 B_l A^{-1} \bmod p$, for each $l$ and each factor base prime $p$
+    bainv2: Vec<Vec<i32>>,
+    /// Roots of the current $Q$ modulo each factor base prime (none for those dividing $A$)
+    solns: Vec<Vec<i32>>,
+    /// Index (from 1) of the current polynomial
+    i: usize,
+}
+
+impl<T: IntegerRing> SelfInitializing<T> {
+    /// * `qs` - Indices into the factor base of the primes making up $A$
+    fn new(fb: &[i32], roots: &[[i32; 2]], qs: &[usize]) -> Self {
+        let a = qs.iter().fold(T::ONE, |a, &l| a * T::from(fb[l]));
+        let bs: Vec<T> = qs
+            .iter()
+            .map(|&l| {
+                let (q, t) = (fb[l], roots[l][0]);
+                let a_l = a.clone() / T::from(q);
+                let a_l_inv = (a_l.rem_small(q as u32) as i32).pow_mod((q - 2) as u32, q);
+                let gamma = t.mul_mod(&a_l_inv, &q);
+                a_l * T::from(gamma.min(q - gamma))
+            })
+            .collect();
+        let b = bs.iter().fold(T::ZERO, |b, b_l| b + b_l.clone());
+        let mut bainv2 = vec![vec![0; fb.len()]; bs.len()];
+        let mut solns = vec![Vec::new(); fb.len()];
+        for (j, (&p, p_roots)) in fb.iter().zip(roots.iter()).enumerate() {
+            let a_p = a.rem_small(p as u32) as i32;
+            if a_p == 0 {
+                continue;
+            }
+            let a_inv = a_p.pow_mod((p - 2) as u32, p);
+            for (l, b_l) in bs.iter().enumerate() {
+                bainv2[l][j] =
+                    (2 * b_l.rem_small(p as u32) as i64 * a_inv as i64 % p as i64) as i32;
+            }
+            // Roots of Q modulo p are x = A^-1 (r - B) for the roots r of n modulo p
+            let b_p = b.rem_small(p as u32) as i32;
+            solns[j] = p_roots
+                .iter()
+                .map(|r| a_inv.mul_mod(&(r - b_p), &p))
+                .collect();
+        }
+        Self {
+            a,
+            b,
+            bs,
+            bainv2,
+            solns,
+            i: 1,
+        }
+    }
+
+    fn polynomial(&self, n: &T) -> Polynomial<T> {
+        let c = (self.b.clone() * self.b.clone() - n.clone()) / self.a.clone();
+        Polynomial {
+            a: self.a.clone(),
+            b: self.b.clone(),
+            c,
+        }
+    }
+
+    /// Switch to the next polynomial, if any remain, updating the roots by additions alone.
+    fn advance(&mut self, fb: &[i32]) -> bool {
+        let i = self.i;
+        if i >= 1 << (self.bs.len() - 1) {
+            return false;
+        }
+        // 2^v exactly divides 2i, and we flip the sign of B_v
+        let v = (2 * i).trailing_zeros() as usize;
+        let positive = ((i + (1 << v) - 1) >> v).is_multiple_of(2);
+        let b_v = self.bs[v - 1].clone() * T::from(2);
+        self.b = if positive {
+            self.b.clone() + b_v
+        } else {
+            self.b.clone() - b_v
+        };
+        for ((&p, solns), &d) in fb
+            .iter()
+            .zip(self.solns.iter_mut())
+            .zip(&self.bainv2[v - 1])
+        {
+            for r in solns.iter_mut() {
+                *r = if positive { *r - d } else { *r + d }.rem_euclid(p);
+            }
+        }
+        self.i += 1;
+        true
+    }
+}
+
+/// Choose factor base indices of primes whose product is near `target`,
+/// at random from the upper part of the factor base.
+fn choose_a<T: IntegerRing>(target: &T, fb: &[i32], rng: &mut StdRng) -> Vec<usize> {
+    let lo = (fb.len() / 4).max(1);
+    let hi = fb.len() - 1;
+    let mut qs: Vec<usize> = Vec::new();
+    let mut a = T::ONE;
+    // pick primes until the remaining factor is below the largest prime
+    while a.clone() * T::from(fb[hi]) <= *target && qs.len() + lo < hi {
+        let l = rng.gen_range(lo..=hi);
+        if !qs.contains(&l) {
+            qs.push(l);
+            a = a * T::from(fb[l]);
+        }
+    }
+    // then pick the prime nearest to the remaining factor
+    let rest = target.clone() / a;
+    let last = (lo..=hi)
+        .filter(|l| !qs.contains(l))
+        .min_by_key(|&l| (T::from(fb[l]) - rest.clone()).abs());
+    qs.extend(last);
+    qs.sort_unstable();
+    qs
+}
+
+/// Self-initializing quadratic sieve
+///
+/// Like [`mpqs`], but with $A$ a product of factor base primes,
+/// which gives $2^{s-1}$ polynomials for each $A$ that are switched between cheaply.
+///
+/// * `n` - Integer to factor
+/// * `b` - Desired factor base length
+/// * `m` - Sieving interval half-width $M$
+/// * `t` - Sieve threshold fudge factor (see [`DEFAULT_FUDGE`])
+///
+/// Returns a non-trivial factor pair `(d, n / d)`.
+pub fn siqs<T: IntegerRing>(n: T, b: usize, m: usize, t: f64) -> Result<(T, T), QsError> {
+    siqs_with_stats(n, b, m, t).0
+}
+
+/// [`siqs`], alongside the [`Stats`] of its relation collection
+/// (all zero if it doesn't get that far).
+pub fn siqs_with_stats<T: IntegerRing>(
+    n: T,
+    b: usize,
+    m: usize,
+    t: f64,
+) -> (Result<(T, T), QsError>, Stats) {
+    let params = QsParams {
+        b,
+        m,
+        t,
+        large_prime_multiplier: LARGE_PRIME_MULTIPLIER,
+    };
+    siqs_with(n, &params)
+}
+
+fn siqs_with<T: IntegerRing>(n: T, params: &QsParams) -> (Result<(T, T), QsError>, Stats) {
+    let state = check(&n).and_then(|()| SiqsState::new(&n, params.large_prime_multiplier));
+    let mut state = match state {
+        Ok(state) => state,
+        Err(e) => return (Err(e), Stats::default()),
+    };
+    let res = state.sieve(params);
+    let stats = state.relations.stats();
+    (res.and_then(|()| find_factor(&n, &state.relations)), stats)
+}
+
+/// Number of polynomials per factor base prime after which [`siqs`] gives up,
+/// if that is more than [`MAX_POLYNOMIALS`]: larger factor bases need more relations,
+/// and each polynomial finds fewer of them as $n$ grows.
+const POLYNOMIALS_PER_PRIME: usize = 50;
+
+/// Relations collected by [`siqs`] and what it needs to carry on collecting them,
+/// so that [`qs_auto`] can enlarge the parameters without starting over.
+struct SiqsState<T> {
+    kn: T,
+    relations: Relations<T>,
+    /// Factor base indices of the primes of each $A$ used so far
+    used: HashSet<Vec<usize>>,
+    rng: StdRng,
+}
+
+impl<T: IntegerRing> SiqsState<T> {
+    fn new(n: &T, large_prime_multiplier: i32) -> Result<Self, QsError> {
+        let kn = multiplied(n)?;
+        Ok(Self {
+            relations: Relations::new(&kn, &[], large_prime_multiplier),
+            kn,
+            used: HashSet::new(),
+            rng: StdRng::seed_from_u64(0),
+        })
+    }
+
+    /// Sieve with `params` until there are enough relations for the factor base,
+    /// which has to be at least as large as that of any earlier call.
+    fn sieve(&mut self, params: &QsParams) -> Result<(), QsError> {
+        let (b, m, t) = (params.b, params.m, params.t);
+        let kn = &self.kn;
+        let (fb, roots) = factor_base_roots(kn, b);
+        if fb.len() < 4 {
+            return Err(QsError::FactorBaseTooSmall {
+                found: fb.len(),
+                needed: 4,
+            });
+        }
+        self.relations
+            .extend_factor_base(&fb, params.large_prime_multiplier);
+        let relations = &mut self.relations;
+        let p_max = *fb.last().unwrap();
+        let needed = fb.len() + 1 + EXTRA_RELATIONS;
+        let target = (kn.clone() * T::from(2)).sqrt() / T::from(m as i32);
+        let max_polynomials = MAX_POLYNOMIALS.max(POLYNOMIALS_PER_PRIME * fb.len());
+        let mut polynomials = 0;
+        while polynomials < max_polynomials && relations.len() < needed {
+            let qs = choose_a(&target, &fb, &mut self.rng);
+            if !self.used.insert(qs.clone()) {
+                // count the repeat too, in case all choices of A are used up
+                polynomials += 1;
+                continue;
+            }
+            let mut family = SelfInitializing::new(&fb, &roots, &qs);
+            loop {
+                polynomials += 1;
+                if !polynomial_fits(&family.a, &family.b, m as i32) {
+                    return Err(QsError::TooLarge);
+                }
+                let poly = family.polynomial(kn);
+                let starts: Vec<Vec<usize>> = fb
+                    .iter()
+                    .zip(family.solns.iter())
+                    .map(|(&p, solns)| {
+                        let m_p = (m % p as usize) as i32;
+                        solns
+                            .iter()
+                            .map(|r| (r + m_p).rem_euclid(p) as usize)
+                            .collect()
+                    })
+                    .collect();
+                let m = m as i32;
+                let threshold = threshold(&poly.max_abs(m), p_max, t);
+                for i in sieve(2 * m as usize, &fb, &starts, threshold) {
+                    let x = i as i32 - m;
+                    // A is not a square, but is smooth, so (Ax + B)^2 = A Q(x) mod n
+                    relations.add(poly.root(x), poly.a.clone() * poly.eval(x), T::ONE);
+                }
+                if relations.len() >= needed || !family.advance(&fb) {
+                    break;
+                }
+            }
+        }
+        if relations.len() >= needed {
+            Ok(())
+        } else {
+            Err(QsError::NotEnoughRelations {
+                found: relations.len(),
+                needed,
+            })
+        }
+    }
+}
+
+/// Sieve parameters, as chosen by [`QsParams::for_n`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QsParams {
+    /// Factor base length
+    pub b: usize,
+    /// Sieving interval half-width $M$
+    pub m: usize,
+    /// Sieve threshold fudge factor
+    pub t: f64,
+    /// Multiplier $k$ for the large prime bound $k p_{max}$
+    pub large_prime_multiplier: i32,
+}
+
+/// Parameters by number of digits: (digits, factor base length, $M$, fudge factor,
+/// large prime multiplier), with the factor base bound growing like $L(n)^{1/2}$
+/// for $L(n) = e^{\sqrt{\ln n \ln \ln n}}$ (scaled down to what works in practice).
+const PARAMS: [(usize, usize, usize, f64, i32); 10] = [
+    (10, 30, 1_000, 2.0, 32),
+    (20, 120, 5_000, 2.0, 48),
+    (25, 200, 10_000, 2.0, 64),
+    (30, 300, 20_000, 2.0, 64),
+    (35, 500, 30_000, 2.2, 64),
+    (40, 800, 40_000, 2.2, 80),
+    (50, 1_800, 65_536, 2.4, 96),
+    (60, 4_000, 98_304, 2.5, 112),
+    (70, 8_000, 131_072, 2.6, 128),
+    (80, 15_000, 196_608, 2.7, 128),
+];
+
+impl QsParams {
+    /// Parameters for factoring `n`, interpolated linearly between
+    /// the rows of a table by the number of digits of `n`
+    /// (and those of the first or last row beyond the table).
+    pub fn for_n<T: IntegerRing>(n: &T) -> Self {
+        let digits = n.abs().to_string().len();
+        let i = PARAMS
+            .iter()
+            .position(|row| row.0 > digits)
+            .unwrap_or(PARAMS.len() - 1)
+            .max(1);
+        let (lo, hi) = (PARAMS[i - 1], PARAMS[i]);
+        let s = (digits.clamp(lo.0, hi.0) - lo.0) as f64 / (hi.0 - lo.0) as f64;
+        let lerp = |a: f64, b: f64| a + s * (b - a);
+        Self {
+            b: lerp(lo.1 as f64, hi.1 as f64).round() as usize,
+            m: lerp(lo.2 as f64, hi.2 as f64).round() as usize,
+            t: lerp(lo.3, hi.3),
+            large_prime_multiplier: lerp(lo.4 as f64, hi.4 as f64).round() as i32,
+        }
     }
 
-    //construct_exponent_matrices(&ys, &fb);
+    /// Parameters with a larger factor base and sieving interval.
+    fn enlarged(&self) -> Self {
+        Self {
+            b: self.b + self.b / 4,
+            m: self.m + self.m / 4,
+            ..*self
+        }
+    }
+}
+
+/// Number of times [`qs_auto`] enlarges the parameters before giving up.
+const MAX_ENLARGEMENTS: usize = 4;
 
-    Ok((0, 0))
+/// Quadratic sieve with parameters chosen from the size of `n` (see [`QsParams::for_n`]).
+///
+/// Uses [`siqs`], and enlarges the factor base and interval if it runs short of relations,
+/// keeping the relations collected so far.
+///
+/// Returns a non-trivial factor pair `(d, n / d)`.
+pub fn qs_auto<T: IntegerRing>(n: T) -> Result<(T, T), QsError> {
+    check(&n)?;
+    let mut params = QsParams::for_n(&n);
+    let mut state = SiqsState::new(&n, params.large_prime_multiplier)?;
+    let mut res = state.sieve(&params);
+    for _ in 0..MAX_ENLARGEMENTS {
+        match res {
+            Err(QsError::NotEnoughRelations { .. }) | Err(QsError::FactorBaseTooSmall { .. }) => {
+                params = params.enlarged();
+                res = state.sieve(&params);
+            }
+            _ => break,
+        }
+    }
+    res.and_then(|()| find_factor(&n, &state.relations))
 }
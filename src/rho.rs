@@ -0,0 +1,63 @@
+use crate::integers::IntegerRing;
+
+/// Number of steps between the gcds in [`brent`].
+const BATCH: usize = 128;
+
+/// Number of polynomials $x^2 + c$ which [`rho`] tries.
+const ATTEMPTS: i32 = 8;
+
+/// Pollard's rho method with Brent's cycle detection, iterating $x \mapsto x^2 + c \bmod n$
+/// from 2 for at most about `max_iterations` steps.
+///
+/// The differences $|x - y|$ are multiplied together so that only one gcd
+/// is taken for every [`BATCH`] steps, backtracking one step at a time when
+/// a batch takes in all of $n$.
+///
+/// Returns a non-trivial factor of $n$, if one is found.
+pub fn brent<T: IntegerRing>(n: &T, c: &T, max_iterations: usize) -> Option<T> {
+    if n.rem_small(2) == 0 {
+        return (n > &T::from(2)).then_some(T::from(2));
+    }
+    let f = |x: &T| (x.mul_mod(x, n) + c.clone()) % n.clone();
+    let (mut x, mut y, mut ys) = (T::from(2), T::from(2), T::from(2));
+    let mut q = T::ONE;
+    let mut g = T::ONE;
+    let mut r = 1;
+    while g == T::ONE {
+        if r > max_iterations {
+            return None;
+        }
+        x = y.clone();
+        for _ in 0..r {
+            y = f(&y);
+        }
+        let mut k = 0;
+        while k < r && g == T::ONE {
+            ys = y.clone();
+            for _ in 0..BATCH.min(r - k) {
+                y = f(&y);
+                q = q.mul_mod(&(x.clone() - y.clone()).abs(), n);
+            }
+            g = q.clone().gcd(n.clone());
+            k += BATCH;
+        }
+        r *= 2;
+    }
+    if &g == n {
+        // the batch overshot, so retrace it one step at a time
+        loop {
+            ys = f(&ys);
+            g = (x.clone() - ys.clone()).abs().gcd(n.clone());
+            if g != T::ONE {
+                break;
+            }
+        }
+    }
+    (&g != n).then_some(g)
+}
+
+/// Find a non-trivial factor of the composite $n$ with [`brent`],
+/// trying a few polynomials for at most about `max_iterations` steps each.
+pub fn rho<T: IntegerRing>(n: &T, max_iterations: usize) -> Option<T> {
+    (1..=ATTEMPTS).find_map(|c| brent(n, &T::from(c), max_iterations))
+}
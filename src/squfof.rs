@@ -0,0 +1,91 @@
+use crate::integers::{Integer, GCD};
+
+/// Multipliers $k$ which [`squfof`] tries in turn, the square-free products of 3, 5, 7 and 11.
+const MULTIPLIERS: [u64; 16] = [
+    1,
+    3,
+    5,
+    7,
+    11,
+    3 * 5,
+    3 * 7,
+    3 * 11,
+    5 * 7,
+    5 * 11,
+    7 * 11,
+    3 * 5 * 7,
+    3 * 5 * 11,
+    3 * 7 * 11,
+    5 * 7 * 11,
+    3 * 5 * 7 * 11,
+];
+
+/// Square root of `n` if it is a perfect square.
+fn exact_sqrt(n: u64) -> Option<u64> {
+    let r = n.sqrt();
+    (r * r == n).then_some(r)
+}
+
+/// One attempt of [`squfof`], on the continued fraction of $\sqrt{kn}$.
+fn squfof_with(n: u64, k: u64) -> Option<u64> {
+    let kn = k.checked_mul(n).filter(|&kn| kn <= i64::MAX as u64)? as i64;
+    let p0 = kn.sqrt();
+    let q0 = kn - p0 * p0;
+    if q0 == 0 {
+        return None;
+    }
+    let bound = 6 * ((2.0 * (n as f64).sqrt()).sqrt() as i64);
+    // forward, until a square form Q_i = r^2 at an even index i
+    let (mut p, mut q_prev, mut q) = (p0, 1, q0);
+    let mut r = None;
+    for i in 2..bound {
+        let b = (p0 + p) / q;
+        let p_next = b * q - p;
+        let q_next = q_prev + b * (p - p_next);
+        q_prev = q;
+        q = q_next;
+        p = p_next;
+        if i % 2 == 0 {
+            r = exact_sqrt(q as u64).map(|r| r as i64);
+            if r.is_some() {
+                break;
+            }
+        }
+    }
+    let r = r?;
+    // then backward from the square root of that form, until P repeats
+    let b = (p0 - p) / r;
+    let mut p = b * r + p;
+    let (mut q_prev, mut q) = (r, (kn - p * p) / r);
+    for _ in 0..bound {
+        let b = (p0 + p) / q;
+        let p_next = b * q - p;
+        let q_next = q_prev + b * (p - p_next);
+        if p_next == p {
+            break;
+        }
+        q_prev = q;
+        q = q_next;
+        p = p_next;
+    }
+    let d = n.gcd(q as u64);
+    (d != 1 && d != n).then_some(d)
+}
+
+/// Shanks' square forms factorization of a 64 bit $n$, in $O(n^{1/4})$ steps
+/// on machine words, trying the [`MULTIPLIERS`] $k$ for which $kn$ fits in 63 bits.
+///
+/// Expands the continued fraction of $\sqrt{kn}$ until one of the forms
+/// $(-1)^i Q_i$ is a square $r^2$, and from it a factor $\gcd(n, Q)$ where
+/// the expansion of the form with root $r$ is symmetric.
+///
+/// Returns a non-trivial factor of $n$, if one is found.
+pub fn squfof(n: u64) -> Option<u64> {
+    if n.is_multiple_of(2) {
+        return (n > 2).then_some(2);
+    }
+    if let Some(r) = exact_sqrt(n) {
+        return (r > 1).then_some(r);
+    }
+    MULTIPLIERS.iter().find_map(|&k| squfof_with(n, k))
+}
@@ -0,0 +1,119 @@
+use std::convert::TryFrom;
+
+use quadratic_sieve::bigint::{I256, I512};
+use quadratic_sieve::prelude::*;
+
+fn big(s: &str) -> I512 {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_parse_display() {
+    for s in &[
+        "0",
+        "1",
+        "-1",
+        "18446744073709551616",
+        "-123456789012345678901234567890",
+    ] {
+        assert_eq!(big(s).to_string(), *s);
+    }
+    assert_eq!(big("-0"), I512::ZERO);
+    assert!("12a".parse::<I512>().is_err());
+}
+
+#[test]
+fn test_matches_i128() {
+    let values: [i128; 8] = [
+        0,
+        1,
+        -1,
+        7,
+        -13,
+        1 << 62,
+        -(1 << 63) + 5,
+        123456789123456789,
+    ];
+    for &a in values.iter() {
+        for &b in values.iter() {
+            let (x, y) = (I256::from(a as i64), I256::from(b as i64));
+            assert_eq!((x + y).to_string(), (a + b).to_string());
+            assert_eq!((x - y).to_string(), (a - b).to_string());
+            assert_eq!((x * y).to_string(), (a * b).to_string());
+            if b != 0 {
+                assert_eq!((x / y).to_string(), (a / b).to_string());
+                assert_eq!((x % y).to_string(), (a % b).to_string());
+            }
+            assert_eq!(x.cmp(&y), a.cmp(&b));
+        }
+    }
+}
+
+#[test]
+fn test_division() {
+    let a = big("1522605027922533360535618378132637429718068114961380688657908494580122963258952897654000350692006139");
+    let b = big("37975227936943673922808872755445627854565536638199");
+    let c = big("40094690950920881030683735292761468389214899724061");
+    assert_eq!(a / b, c);
+    assert_eq!(a % b, I512::ZERO);
+    let d = a + I512::from(12345);
+    assert_eq!(d % c, I512::from(12345));
+}
+
+#[test]
+fn test_sqrt() {
+    let a = big("37975227936943673922808872755445627854565536638199");
+    assert_eq!((a * a).sqrt(), a);
+    assert_eq!((a * a - I512::ONE).sqrt(), a - I512::ONE);
+    assert_eq!(I512::from(99).sqrt(), I512::from(9));
+    assert_eq!(1_000_000_007i64.squared().sqrt(), 1_000_000_007);
+}
+
+#[test]
+fn test_bit_length() {
+    assert_eq!(I512::ZERO.bit_length(), 0);
+    assert_eq!(
+        big("-340282366920938463463374607431768211456").bit_length(),
+        129
+    );
+    assert_eq!(0i32.bit_length(), 0);
+    assert_eq!(((1i64 << 24) - 1).bit_length(), 24);
+    assert_eq!(i64::MIN.bit_length(), 64);
+}
+
+#[test]
+fn test_gcd() {
+    let p = big("37975227936943673922808872755445627854565536638199");
+    let q = big("40094690950920881030683735292761468389214899724061");
+    assert_eq!((p * q).gcd(p * I512::from(6)), p);
+    assert_eq!(I512::from(-12).gcd(I512::from(18)), I512::from(6));
+}
+
+#[test]
+fn test_mul_mod_pow_mod() {
+    let m = big("340282366920938463463374607431768211507"); // next prime after 2^128
+    let a = m - I512::ONE;
+    assert_eq!(a.mul_mod(&a, &m), I512::ONE);
+    assert_eq!(I512::from(-3).mul_mod(&I512::ONE, &m), m - I512::from(3));
+    // Fermat's little theorem, with an exponent fitting u32
+    assert_eq!(
+        I512::from(3).pow_mod(2147483646, I512::from(2147483647)),
+        I512::ONE
+    );
+    assert_eq!(70000i32.pow_mod(2, 70001), 1);
+    assert_eq!(big("-7").rem_small(5), 3);
+}
+
+#[test]
+fn test_conversions() {
+    assert_eq!(i64::try_from(I256::from(i64::MIN)).unwrap(), i64::MIN);
+    assert_eq!(u64::try_from(I256::from(u64::MAX)).unwrap(), u64::MAX);
+    assert!(u64::try_from(I256::from(-1)).is_err());
+}
+
+#[test]
+#[should_panic]
+fn test_overflow() {
+    let a = I256::ONE << 255;
+    let _ = a * I256::from(2);
+}
@@ -0,0 +1,64 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use quadratic_sieve::prelude::*;
+
+fn random_bits(rng: &mut StdRng, m: usize, n: usize) -> Vec<Vec<bool>> {
+    (0..m)
+        .map(|_| (0..n).map(|_| rng.gen_bool(0.1)).collect())
+        .collect()
+}
+
+#[test]
+fn test_augment() {
+    let a = BitMatrix::from_bits(&[vec![true, false], vec![true, true]]);
+    let res = BitMatrix::from_bits(&[
+        vec![true, false, true, false],
+        vec![true, true, false, true],
+    ]);
+    assert_eq!(a.augment(), res);
+}
+
+#[test]
+fn test_lnull_span_zz2() {
+    #[rustfmt::skip]
+    let a = BitMatrix::from_bits(&[
+        vec![false, false, false, true],
+        vec![false, false, false, false],
+        vec![true, true, true, false],
+        vec![true, true, true, true],
+    ]);
+    let mut i = a.iter_lnull_span();
+    assert_eq!(i.next().unwrap(), vec![true, false, true, true]);
+    assert_eq!(i.next().unwrap(), vec![false, true, false, false]);
+    assert!(i.next().is_none());
+}
+
+#[test]
+fn test_matches_dyn_matrix() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let a = BitMatrix::from_bits(&random_bits(&mut rng, 90, 70));
+    let b = DynMatrix::<CongruenceClass<2>>::from(&a);
+    assert_eq!(BitMatrix::from(&b.to_echelon()), a.to_echelon());
+    let expected: Vec<Vec<bool>> = b
+        .iter_lnull_span()
+        .map(|v| v.iter().map(|x| x == &cc!(1, 2)).collect())
+        .collect();
+    assert_eq!(a.iter_lnull_span().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn test_large_dependencies() {
+    let (m, n) = (1200, 1100);
+    let mut rng = StdRng::seed_from_u64(1);
+    let bits = random_bits(&mut rng, m, n);
+    let a = BitMatrix::from_bits(&bits);
+    let deps: Vec<Vec<bool>> = a.iter_lnull_span().collect();
+    assert!(deps.len() >= m - n);
+    for v in deps {
+        let mut sum = vec![false; n];
+        for row in (0..m).filter(|&i| v[i]).map(|i| &bits[i]) {
+            sum.iter_mut().zip(row.iter()).for_each(|(a, b)| *a ^= b);
+        }
+        assert!(sum.iter().all(|a| !a));
+    }
+}
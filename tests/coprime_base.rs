@@ -0,0 +1,49 @@
+use quadratic_sieve::coprime_base::coprime_base;
+
+#[test]
+fn test_coprime_base() {
+    assert_eq!(coprime_base::<i64>(&[]), (vec![], vec![]));
+    assert_eq!(coprime_base(&[1i64]), (vec![], vec![vec![]]));
+    assert_eq!(
+        coprime_base(&[6i64, 10]),
+        (vec![2, 3, 5], vec![vec![1, 1, 0], vec![1, 0, 1]])
+    );
+    // 12 = 2^2 3 and 18 = 2 3^2 only share 6, but give 2 and 3 apart
+    assert_eq!(
+        coprime_base(&[12i64, 18]),
+        (vec![2, 3], vec![vec![2, 1], vec![1, 2]])
+    );
+    // coprime inputs are left whole, and repeats merged
+    assert_eq!(
+        coprime_base(&[35i64, 6, 35]),
+        (vec![6, 35], vec![vec![0, 1], vec![1, 0], vec![0, 1]])
+    );
+}
+
+#[test]
+fn test_coprime_base_products() {
+    let ns = [
+        1009i64 * 1013 * 1013,
+        1013 * 1019,
+        1009 * 1019 * 1021,
+        1021i64.pow(4),
+        1009 * 1013 * 1019 * 1021,
+    ];
+    let (base, exponents) = coprime_base(&ns);
+    assert_eq!(base, vec![1009, 1013, 1019, 1021]);
+    for (n, e) in ns.iter().zip(exponents.iter()) {
+        let product: i64 = base.iter().zip(e.iter()).map(|(b, &e)| b.pow(e)).product();
+        assert_eq!(&product, n);
+    }
+    for i in 0..base.len() {
+        for j in 0..i {
+            assert_eq!(quadratic_sieve::integers::GCD::gcd(base[i], base[j]), 1);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_coprime_base_zero() {
+    coprime_base(&[6i64, 0]);
+}
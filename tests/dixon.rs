@@ -0,0 +1,43 @@
+use quadratic_sieve::dixon::{dixon, dixon_with_stats};
+use quadratic_sieve::prelude::*;
+
+#[test]
+fn test_dixon() {
+    // every prime is in the factor base, since x^2 mod n takes any residue modulo it
+    let (d, e) = dixon(101i64 * 103, 10, 10_000).unwrap();
+    assert!(d == 101 || d == 103, "{} {}", d, e);
+    let (d, e) = dixon(1000003i64 * 1000033, 60, 100_000).unwrap();
+    assert!(d == 1000003 || d == 1000033, "{} {}", d, e);
+}
+
+#[test]
+fn test_dixon_errors() {
+    assert_eq!(dixon(1000i64, 30, 1000), Err(QsError::Even));
+    assert_eq!(dixon(1000003i64, 30, 1000), Err(QsError::Prime));
+    assert_eq!(dixon(1009i64 * 1009, 30, 1000), Err(QsError::PerfectPower));
+    assert!(matches!(
+        dixon(1000003i64 * 1000033, 30, 10),
+        Err(QsError::NotEnoughRelations { .. })
+    ));
+}
+
+#[test]
+fn test_dixon_stats() {
+    let n = 1000003i64 * 1000033;
+    let (res, stats) = dixon_with_stats(n, 100, 100_000);
+    assert!(res.is_ok());
+    assert_eq!(stats.factor_base, 101);
+    assert!(stats.full + stats.cycles > stats.factor_base);
+    assert!(stats.full + stats.partial <= stats.candidates);
+    // the sieve picks out its candidates, which are also much smaller than the x^2 mod n,
+    // so far more of them give relations
+    let (res, qs_stats) = qs_with_stats(n, 100, 4000, DEFAULT_FUDGE);
+    assert!(res.is_ok());
+    let found = |s: &Stats| (s.full + s.cycles) as f64 / s.candidates as f64;
+    assert!(
+        found(&qs_stats) > 10.0 * found(&stats),
+        "{} {}",
+        qs_stats,
+        stats
+    );
+}
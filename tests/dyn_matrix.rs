@@ -0,0 +1,92 @@
+use std::convert::TryFrom;
+
+use quadratic_sieve::prelude::*;
+
+#[test]
+fn test_addition() {
+    let a = DynMatrix::from_value(2, 3, 1);
+    let b = DynMatrix::from_value(2, 3, 2);
+    let res = DynMatrix::from_value(2, 3, 3);
+    assert_eq!(a + b, res);
+}
+
+#[test]
+fn test_multiplication() {
+    #[rustfmt::skip]
+    let a = DynMatrix::<i32>::from_vec(vec![
+        vec![1, 2, 0],
+        vec![0, 0, 1],
+    ]);
+    #[rustfmt::skip]
+    let b = DynMatrix::<i32>::from_vec(vec![
+        vec![2, 0],
+        vec![1, 0],
+        vec![2, 2],
+    ]);
+    #[rustfmt::skip]
+    let res = DynMatrix::<i32>::from_vec(vec![
+        vec![4, 0],
+        vec![2, 2],
+    ]);
+    assert_eq!(a * b, res);
+}
+
+#[test]
+fn test_transpose() {
+    let a = DynMatrix::<i32>::from_vec(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    let res = DynMatrix::<i32>::from_vec(vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+    assert_eq!(a.transpose(), res);
+}
+
+#[test]
+fn test_augment() {
+    #[rustfmt::skip]
+    let a = DynMatrix::<i32>::from_vec(vec![
+        vec![0, 1],
+        vec![2, 3],
+        vec![4, 5],
+    ]);
+    #[rustfmt::skip]
+    let res = DynMatrix::<i32>::from_vec(vec![
+        vec![0, 1, 1, 0, 0],
+        vec![2, 3, 0, 1, 0],
+        vec![4, 5, 0, 0, 1],
+    ]);
+    assert_eq!(a.augment(), res)
+}
+
+#[test]
+fn test_to_echelon_matches_matrix() {
+    #[rustfmt::skip]
+    let a = Matrix::<i32, 3, 4>::from_array([
+        [0, 0, 3, 1],
+        [2, 2, 1, 1],
+        [1, 3, 3, 3],
+    ]);
+    let b = DynMatrix::from(a.clone());
+    assert_eq!(b.to_echelon(), DynMatrix::from(a.to_echelon()));
+}
+
+#[test]
+fn test_lnull_span_zz2() {
+    #[rustfmt::skip]
+    let a = Matrix::from_array(cc_array!(2, [
+        [0, 0, 0, 1],
+        [0, 0, 0, 0],
+        [1, 1, 1, 0],
+        [1, 1, 1, 1],
+    ]));
+    let mut i = DynMatrix::from(a).iter_lnull_span();
+    assert_eq!(i.next().unwrap(), cc_array!(2, [1, 0, 1, 1]));
+    assert_eq!(i.next().unwrap(), cc_array!(2, [0, 1, 0, 0]));
+    assert!(i.next().is_none());
+}
+
+#[test]
+fn test_conversions() {
+    let a = Matrix::<i32, 2, 2>::from_array([[1, 2], [3, 4]]);
+    let b = DynMatrix::from(a.clone());
+    assert_eq!(b.shape(), (2, 2));
+    assert_eq!(Matrix::<i32, 2, 2>::try_from(b.clone()).unwrap(), a);
+    assert!(Matrix::<i32, 2, 3>::try_from(b).is_err());
+}
@@ -0,0 +1,43 @@
+use quadratic_sieve::bigint::I256;
+use quadratic_sieve::ecm::ecm;
+use quadratic_sieve::pm1::{pm1, pp1};
+
+#[test]
+fn test_ecm_curves() {
+    // 1000000433 * 1000000447, where p - 1 and p + 1 have prime factors above 10^6 for both
+    let n = 1000000880000193551i64;
+    assert_eq!(pm1(&n, 2000, 200_000), None);
+    assert_eq!(pp1(&n, 2000, 200_000), None);
+    // the first 52 curves have an order with a prime factor above B2 modulo both
+    assert_eq!(ecm(&n, 100, 1000, 52), None);
+    assert_eq!(ecm(&n, 100, 1000, 53), Some(1000000433));
+    // a larger B2 takes in more of the orders, and a larger B1 more still
+    assert_eq!(ecm(&n, 100, 10_000, 2), None);
+    assert_eq!(ecm(&n, 100, 10_000, 3), Some(1000000433));
+    assert_eq!(ecm(&n, 500, 50_000, 1), Some(1000000447));
+    assert_eq!(ecm(&(2 * 1009i64), 100, 1000, 1), Some(2));
+    assert_eq!(ecm(&1000003i64, 100, 1000, 5), None);
+}
+
+#[test]
+fn test_ecm_stage2_wide() {
+    // B2 much more than B1^2, so that the stage 2 step is bounded by 2 B1
+    let n = 1000000880000193551i64;
+    assert_eq!(ecm(&n, 10, 1000, 2), None);
+    assert_eq!(ecm(&n, 1, 2, 1), None);
+    assert_eq!(ecm(&n, 20, 1_000_000, 6), None);
+    assert_eq!(ecm(&n, 20, 1_000_000, 7), Some(1000000447));
+}
+
+#[test]
+fn test_ecm_bigint() {
+    // 1000000000061 * 100000000000012531, where p - 1 and p + 1
+    // have prime factors above 10^7 for both
+    let n: I256 = "100000000006112531000000764391".parse().unwrap();
+    assert_eq!(pm1(&n, 2000, 200_000), None);
+    assert_eq!(pp1(&n, 2000, 200_000), None);
+    assert_eq!(
+        ecm(&n, 2000, 200_000, 50),
+        Some("1000000000061".parse().unwrap())
+    );
+}
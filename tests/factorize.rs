@@ -0,0 +1,73 @@
+use quadratic_sieve::prelude::*;
+
+fn product(factors: &[(i64, u32)]) -> i64 {
+    factors.iter().map(|&(p, e)| p.pow(e)).product()
+}
+
+#[test]
+fn test_factorize_small() {
+    assert_eq!(factorize(0i64), vec![]);
+    assert_eq!(factorize(1i64), vec![]);
+    assert_eq!(factorize(-12i64), vec![(2, 2), (3, 1)]);
+    for n in 2..2000i64 {
+        let factors = factorize(n);
+        assert_eq!(product(&factors), n);
+        assert!(factors.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(factors.iter().all(|&(p, _)| (2..p).all(|d| p % d != 0)));
+    }
+}
+
+#[test]
+fn test_factorize_prime_power() {
+    // 1000003^2 * 1000033 and 1009^3, past trial division
+    assert_eq!(
+        factorize(1000003i64 * 1000003 * 1000033),
+        vec![(1000003, 2), (1000033, 1)]
+    );
+    assert_eq!(factorize(1000003i64.pow(3)), vec![(1000003, 3)]);
+    // split parts sharing primes, whose refinement has to merge them across splits
+    use quadratic_sieve::bigint::I256;
+    let (p, q, r) = (
+        I256::from(1000003),
+        I256::from(1000033),
+        I256::from(1000037),
+    );
+    assert_eq!(
+        factorize(p.pow(2) * q.pow(3) * r),
+        vec![(p, 2), (q, 3), (r, 1)]
+    );
+    assert_eq!(
+        factorize(2i64.pow(10) * 1009i64.pow(3)),
+        vec![(2, 10), (1009, 3)]
+    );
+}
+
+#[test]
+fn test_factorize_bigint() {
+    use quadratic_sieve::bigint::I256;
+
+    // 2^3 * 3 * 1000000007 * 1000000009 * 100000007
+    let n: I256 = "2400000206400002839200010584".parse::<I256>().unwrap();
+    let factors = factorize(n);
+    let expected: Vec<(I256, u32)> = vec![
+        (2.into(), 3),
+        (3.into(), 1),
+        (100000007.into(), 1),
+        (1000000007.into(), 1),
+        (1000000009.into(), 1),
+    ];
+    assert_eq!(factors, expected);
+}
+
+#[test]
+fn test_factorize_smooth_order() {
+    use quadratic_sieve::bigint::I256;
+
+    // 140984951201659951 * 100000000000012531, where the first less 1 is 100-smooth
+    let n: I256 = "14098495120167761782423508000845981".parse().unwrap();
+    let expected: Vec<(I256, u32)> = vec![
+        ("100000000000012531".parse().unwrap(), 1),
+        ("140984951201659951".parse().unwrap(), 1),
+    ];
+    assert_eq!(factorize(n), expected);
+}
@@ -0,0 +1,16 @@
+use quadratic_sieve::fermat::fermat;
+
+#[test]
+fn test_fermat() {
+    assert_eq!(fermat(&811i64), (811, 1));
+    assert_eq!(fermat(&813i64), (271, 3));
+    assert_eq!(fermat(&(1000003i64 * 1000033)), (1000033, 1000003));
+    assert_eq!(fermat(&(1009i64 * 1009)), (1009, 1009));
+    assert_eq!(fermat(&1i64), (1, 1));
+}
+
+#[test]
+#[should_panic]
+fn test_fermat_even() {
+    fermat(&10i64);
+}
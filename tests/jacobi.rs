@@ -0,0 +1,72 @@
+use quadratic_sieve::bigint::I256;
+use quadratic_sieve::integers::Integer;
+use quadratic_sieve::jacobi::{jacobi, kronecker};
+use quadratic_sieve::primes::primes_in;
+
+#[test]
+fn test_jacobi_legendre() {
+    // Euler's criterion for odd primes
+    for p in primes_in(3..200) {
+        let p = p as i64;
+        for a in -50..50i64 {
+            let euler = match a.pow_mod(((p - 1) / 2) as u32, p) {
+                0 => 0,
+                1 => 1,
+                _ => -1,
+            };
+            assert_eq!(jacobi(&a, &p), euler, "({} / {})", a, p);
+        }
+    }
+}
+
+#[test]
+fn test_jacobi_composite() {
+    // multiplicative in n
+    for m in (1..60i32).step_by(2) {
+        for n in (1..60i32).step_by(2) {
+            for a in -20..20 {
+                let mn = jacobi(&a, &(m * n));
+                assert_eq!(mn, jacobi(&a, &m) * jacobi(&a, &n), "{} {} {}", a, m, n);
+            }
+        }
+    }
+    assert_eq!(jacobi(&1001, &9907), -1);
+    assert_eq!(jacobi(&19, &45), 1);
+    assert_eq!(jacobi(&8, &21), -1);
+    assert_eq!(jacobi(&5, &21), 1);
+    assert_eq!(jacobi(&7, &1), 1);
+    assert_eq!(jacobi(&6, &15), 0);
+    let n: I256 = "100000000000012531".parse().unwrap();
+    let a: I256 = "140984951201659951".parse().unwrap();
+    assert_eq!(
+        jacobi(&a, &n),
+        jacobi(&140984951201659951i64, &100000000000012531)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_jacobi_even() {
+    jacobi(&3, &10);
+}
+
+#[test]
+fn test_kronecker() {
+    assert_eq!(kronecker(&1, &0), 1);
+    assert_eq!(kronecker(&-1, &0), 1);
+    assert_eq!(kronecker(&2, &0), 0);
+    assert_eq!(kronecker(&5, &-1), 1);
+    assert_eq!(kronecker(&-5, &-1), -1);
+    assert_eq!(kronecker(&3, &2), -1);
+    assert_eq!(kronecker(&7, &2), 1);
+    assert_eq!(kronecker(&-3, &2), -1);
+    assert_eq!(kronecker(&4, &2), 0);
+    assert_eq!(kronecker(&5, &12), -1);
+    assert_eq!(kronecker(&-5, &-12), -1);
+    // agrees with the Jacobi symbol for odd positive n
+    for n in (1..100i32).step_by(2) {
+        for a in -30..30 {
+            assert_eq!(kronecker(&a, &n), jacobi(&a, &n));
+        }
+    }
+}
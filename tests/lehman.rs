@@ -0,0 +1,25 @@
+use quadratic_sieve::lehman::lehman;
+
+#[test]
+fn test_lehman() {
+    // a factor below n^(1/3), found by trial division
+    assert_eq!(lehman(307 * 10000000019), Some(307));
+    // an unbalanced split just above n^(1/3), which Fermat's method would take
+    // far too long over, and a balanced one, both below 2^45
+    for &(p, q) in [(21557u64, 462962963u64), (3162283, 3162317)].iter() {
+        let d = lehman(p * q).unwrap();
+        assert!(d == p || d == q, "{} {} {}", p, q, d);
+    }
+    assert_eq!(lehman(2 * 1009), Some(2));
+    assert_eq!(lehman(1009 * 1009), Some(1009));
+    assert_eq!(lehman(1000003), None);
+}
+
+#[test]
+fn test_lehman_exhaustive() {
+    // deterministic below 2^45, so it splits every composite
+    for n in 4..5000u64 {
+        let composite = (2..n).any(|d| n % d == 0);
+        assert_eq!(lehman(n).is_some(), composite, "{}", n);
+    }
+}
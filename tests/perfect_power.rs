@@ -0,0 +1,47 @@
+use quadratic_sieve::bigint::I256;
+use quadratic_sieve::integers::Integer;
+use quadratic_sieve::perfect_power::{is_perfect_power, nth_root, perfect_power};
+
+#[test]
+fn test_nth_root() {
+    assert_eq!(nth_root(&0i64, 3), 0);
+    assert_eq!(nth_root(&1i64, 3), 1);
+    assert_eq!(nth_root(&26i64, 3), 2);
+    assert_eq!(nth_root(&27i64, 3), 3);
+    assert_eq!(nth_root(&(i64::MAX), 2), 3037000499);
+    assert_eq!(nth_root(&(i64::MAX), 62), 2);
+    assert_eq!(nth_root(&(i64::MAX), 63), 1);
+    for n in 1..2000i64 {
+        for k in 1..12 {
+            let r = nth_root(&n, k);
+            assert!(r.pow(k) <= n && (r + 1).pow(k) > n, "{} {}", n, k);
+        }
+    }
+}
+
+#[test]
+fn test_perfect_power() {
+    assert_eq!(perfect_power(&2i64), (2, 1));
+    assert_eq!(perfect_power(&(1009i64 * 1009)), (1009, 2));
+    assert_eq!(perfect_power(&(1i64 << 60)), (2, 60));
+    assert_eq!(perfect_power(&6i64.pow(12)), (6, 12));
+    assert_eq!(
+        perfect_power(&(1009i64.pow(5) + 2)),
+        (1009i64.pow(5) + 2, 1)
+    );
+    assert_eq!(
+        perfect_power(&(1000003i64 * 1000033)),
+        (1000003 * 1000033, 1)
+    );
+    assert!(is_perfect_power(&(3i64.pow(39))));
+    assert!(!is_perfect_power(&(3i64.pow(39) - 2)));
+}
+
+#[test]
+fn test_perfect_power_bigint() {
+    // 1000000007^7, and one less, beyond where float roots are exact
+    let r = I256::from(1000000007);
+    let n = r.pow(7);
+    assert_eq!(perfect_power(&n), (r, 7));
+    assert_eq!(perfect_power(&(n - I256::from(1))).1, 1);
+}
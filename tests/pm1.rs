@@ -0,0 +1,61 @@
+use quadratic_sieve::bigint::I256;
+use quadratic_sieve::pm1::{pm1, pp1};
+
+fn parse(s: &str) -> I256 {
+    s.parse().unwrap()
+}
+
+// q = 100000000000012531 has q - 1 and q + 1 both with prime factors above 10^11
+
+#[test]
+fn test_pm1() {
+    // p = 140984951201659951, p - 1 = 2 * 3 * 5^2 * 7 * 11 * 19 * 29 * 31 * 37 * 41 * 67 * 79 * 89
+    let n = parse("14098495120167761782423508000845981");
+    let p = parse("140984951201659951");
+    assert_eq!(pm1(&n, 100, 100), Some(p));
+    assert_eq!(pm1(&n, 50, 50), None);
+    assert_eq!(pm1(&1000003i64, 100, 1000), None);
+    assert_eq!(pm1(&(2 * 1009i64), 100, 1000), Some(2));
+}
+
+#[test]
+fn test_pm1_stage2() {
+    // p = 1128003583015297787, p - 1 = 2 * 7 * 19 * 23 * 37 * 47 * 59 * 61 * 71 * 83 * 4999
+    let n = parse("112800358301543913712898764696568897");
+    let p = parse("1128003583015297787");
+    assert_eq!(pm1(&n, 100, 100), None);
+    assert_eq!(pm1(&n, 100, 4998), None);
+    assert_eq!(pm1(&n, 100, 5000), Some(p));
+}
+
+#[test]
+fn test_pp1() {
+    // p = 110197836030464069, p + 1 = 2 * 3 * 5 * 7 * 19 * 23 * 29 * 43 * 47 * 59 * 67 * 71 * 73
+    let n = parse("11019783603047787789083297745248639");
+    let p = parse("110197836030464069");
+    assert_eq!(pm1(&n, 100, 10000), None);
+    assert_eq!(pp1(&n, 100, 100), Some(p));
+    assert_eq!(pp1(&1000003i64, 100, 1000), None);
+}
+
+#[test]
+fn test_pp1_stage2() {
+    // p = 618879561636688169, p + 1 = 2 * 3 * 5 * 7 * 13 * 37 * 41 * 47 * 79 * 83 * 97 * 4999
+    let n = parse("61887956163676572079786869339445739");
+    let p = parse("618879561636688169");
+    assert_eq!(pp1(&n, 100, 4998), None);
+    assert_eq!(pp1(&n, 100, 5000), Some(p));
+}
+
+#[test]
+fn test_pp1_stage2_wide() {
+    // p = 474263, p + 1 = 2^3 * 3^2 * 7 * 941, with B2 much more than B1^2
+    let n = 474263 * 1000003i64;
+    assert_eq!(pp1(&n, 10, 940), None);
+    assert_eq!(pp1(&n, 10, 1000), Some(474263));
+    // p = 821727511458300913, p + 1 = 2 * 337 * 409 * 661 * 919 * 4907131
+    let n = parse("82172751145840388367446083968740803");
+    let p = parse("821727511458300913");
+    assert_eq!(pp1(&n, 1000, 4_900_000), None);
+    assert_eq!(pp1(&n, 1000, 5_000_000), Some(p));
+}
@@ -0,0 +1,56 @@
+use quadratic_sieve::bigint::I256;
+use quadratic_sieve::primality::{
+    is_prime, is_strong_lucas_probable_prime, is_strong_probable_prime,
+};
+
+#[test]
+fn test_is_prime_small() {
+    let mut sieve = vec![true; 10000];
+    sieve[0] = false;
+    sieve[1] = false;
+    for i in 2..sieve.len() {
+        for j in (2 * i..sieve.len()).step_by(i) {
+            sieve[j] = false;
+        }
+    }
+    for (n, &p) in sieve.iter().enumerate() {
+        assert_eq!(is_prime(&(n as i32)), p, "{}", n);
+    }
+    assert!(!is_prime(&-7));
+}
+
+#[test]
+fn test_is_prime_pseudoprimes() {
+    // strong pseudoprimes to base 2
+    for &n in [2047i64, 3277, 4033, 4681, 8321, 3215031751].iter() {
+        assert!(is_strong_probable_prime(&n, &2));
+        assert!(!is_prime(&n));
+    }
+    // Carmichael numbers
+    for &n in [561i64, 1105, 1729, 2465, 2821, 6601, 8911].iter() {
+        assert!(!is_prime(&n));
+    }
+    // strong pseudoprime to all prime bases up to 23
+    assert!(!is_prime(&3825123056546413051i64));
+    assert!(is_prime(&((1i64 << 61) - 1)));
+    assert!(is_prime(&(i64::MAX - 24)));
+}
+
+#[test]
+fn test_is_prime_bpsw() {
+    let parse = |s: &str| s.parse::<I256>().unwrap();
+    // Mersenne primes 2^89 - 1 and 2^127 - 1, and composite 2^67 - 1
+    assert!(is_prime(&parse("618970019642690137449562111")));
+    assert!(is_prime(&parse("170141183460469231731687303715884105727")));
+    assert!(!is_prime(&parse("147573952589676412927")));
+    // strong pseudoprime to all prime bases up to 37, but not a strong Lucas probable prime
+    let n = parse("318665857834031151167461");
+    assert!(is_strong_probable_prime(&n, &2.into()));
+    assert!(!is_strong_lucas_probable_prime(&n));
+    assert!(!is_prime(&n));
+    // product of two primes, and a square of one
+    let p = parse("1000000000000000000000007");
+    assert!(is_prime(&p));
+    assert!(!is_prime(&(p * parse("1000000000000000000000049"))));
+    assert!(!is_prime(&(p * p)));
+}
@@ -0,0 +1,45 @@
+use std::ops::Range;
+
+use quadratic_sieve::primality::is_prime;
+use quadratic_sieve::primes::{nth_prime, primes_from, primes_in};
+
+#[test]
+fn test_primes_in() {
+    let expected: Vec<u64> = (0..10_000u64).filter(|&n| is_prime(&(n as i64))).collect();
+    assert_eq!(primes_in(0..10_000), expected);
+    assert_eq!(primes_in(0..2), vec![]);
+    assert_eq!(primes_in(2..3), vec![2]);
+    assert_eq!(primes_in(3..30), expected[1..10].to_vec());
+    assert_eq!(primes_in(Range { start: 30, end: 3 }), vec![]);
+    // several segments, far from the start
+    let lo = 1_000_000_000;
+    let expected: Vec<u64> = (lo..lo + 3_000_000)
+        .filter(|&n| is_prime(&(n as i64)))
+        .collect();
+    assert_eq!(primes_in(lo..lo + 3_000_000), expected);
+    // above 2^32, where the sieving primes are sieved by segments too
+    let lo = 1 << 40;
+    let expected: Vec<u64> = (lo..lo + 100_000)
+        .filter(|&n| is_prime(&(n as i64)))
+        .collect();
+    assert_eq!(primes_in(lo..lo + 100_000), expected);
+}
+
+#[test]
+fn test_primes_from() {
+    let primes: Vec<u64> = primes_from(0).take(10).collect();
+    assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    for &x in [4u64, 31, 997, 123_456_789].iter() {
+        let expected = primes_in(x..x + 100_000);
+        let primes: Vec<u64> = primes_from(x).take(expected.len()).collect();
+        assert_eq!(primes, expected, "{}", x);
+    }
+}
+
+#[test]
+fn test_nth_prime() {
+    assert_eq!(nth_prime(1), 2);
+    assert_eq!(nth_prime(4), 7);
+    assert_eq!(nth_prime(1000), 7919);
+    assert_eq!(nth_prime(100_000), 1_299_709);
+}
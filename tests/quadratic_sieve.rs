@@ -0,0 +1,195 @@
+use quadratic_sieve::prelude::*;
+
+#[test]
+fn test_qs_small() {
+    // with so few primes most smooth values are divisible by prime powers,
+    // which the sieve undercounts, so the threshold is lowered
+    let (a, b) = qs(16843009, 6, 300, 4.0).unwrap();
+    assert_eq!(a * b, 16843009);
+    assert!(a != 1 && b != 1);
+}
+
+#[test]
+fn test_qs_semiprime() {
+    let n = 1009 * 2003;
+    let (a, b) = qs(n, 20, 2000, DEFAULT_FUDGE).unwrap();
+    assert_eq!(a * b, n);
+    assert!(a == 1009 || a == 2003);
+}
+
+#[test]
+fn test_qs_too_few_relations() {
+    let (res, stats) = qs_with_stats(1009 * 2003, 20, 10, DEFAULT_FUDGE);
+    let err = res.unwrap_err();
+    match err {
+        // a dependency needs more relations than columns, -1 and the factor base primes
+        QsError::NotEnoughRelations { found, needed } => {
+            assert!(found < needed);
+            assert_eq!(needed, stats.factor_base + 1);
+        }
+        _ => panic!("unexpected error {}", err),
+    }
+}
+
+#[test]
+fn test_qs_errors() {
+    for &n in [-1009 * 2003, -1, 0, 1].iter() {
+        assert_eq!(qs(n, 20, 2000, DEFAULT_FUDGE), Err(QsError::TooSmall));
+        assert_eq!(mpqs(n, 20, 2000, DEFAULT_FUDGE), Err(QsError::TooSmall));
+        assert_eq!(siqs(n, 20, 2000, DEFAULT_FUDGE), Err(QsError::TooSmall));
+    }
+    assert_eq!(qs(2 * 1009, 20, 2000, DEFAULT_FUDGE), Err(QsError::Even));
+    assert_eq!(qs(1000003, 20, 2000, DEFAULT_FUDGE), Err(QsError::Prime));
+    assert_eq!(
+        qs(1009 * 1009, 20, 2000, DEFAULT_FUDGE),
+        Err(QsError::PerfectPower)
+    );
+    assert_eq!(
+        qs(1009 * 1009 * 1009, 20, 2000, DEFAULT_FUDGE),
+        Err(QsError::PerfectPower)
+    );
+    // sieve values of up to 2n and more don't fit an i32, but do an i64
+    let n = 32749 * 32719;
+    assert_eq!(qs(n, 20, 2000, DEFAULT_FUDGE), Err(QsError::TooLarge));
+    assert_eq!(mpqs(n, 20, 2000, DEFAULT_FUDGE), Err(QsError::TooLarge));
+    assert_eq!(siqs(n, 20, 2000, DEFAULT_FUDGE), Err(QsError::TooLarge));
+    assert_eq!(qs_auto(n), Err(QsError::TooLarge));
+    assert!(qs_auto(n as i64).is_ok());
+    // and so do the squares over an interval much wider than sqrt(n)
+    let n = 1009 * 1013;
+    assert!(qs(n, 20, 2000, DEFAULT_FUDGE).is_ok());
+    assert_eq!(qs(n, 20, 200_000, DEFAULT_FUDGE), Err(QsError::TooLarge));
+    assert_eq!(mpqs(n, 20, 200_000, DEFAULT_FUDGE), Err(QsError::TooLarge));
+    assert_eq!(siqs(n, 20, 200_000, DEFAULT_FUDGE), Err(QsError::TooLarge));
+    assert_eq!(
+        siqs(1009 * 2003, 2, 100, DEFAULT_FUDGE),
+        Err(QsError::FactorBaseTooSmall {
+            found: 2,
+            needed: 4
+        })
+    );
+}
+
+#[test]
+fn test_qs_i64() {
+    let n: i64 = 1000003 * 1000033;
+    let (a, b) = qs(n, 100, 40000, DEFAULT_FUDGE).unwrap();
+    assert_eq!(a * b, n);
+    assert!(a == 1000003 || a == 1000033);
+}
+
+#[test]
+fn test_qs_bigint() {
+    use quadratic_sieve::bigint::I256;
+
+    let n: I256 = "10000004400000259".parse().unwrap(); // 100000007 * 100000037
+    let (a, b) = qs(n, 100, 100000, DEFAULT_FUDGE).unwrap();
+    assert_eq!(a * b, n);
+    assert!(a != I256::ONE && b != I256::ONE);
+}
+
+#[test]
+fn test_mpqs() {
+    use quadratic_sieve::bigint::I256;
+
+    let n: I256 = "1000000016000000063".parse().unwrap(); // 1000000007 * 1000000009
+    let (a, b) = mpqs(n, 200, 10000, DEFAULT_FUDGE).unwrap();
+    assert_eq!(a * b, n);
+    assert!(a != I256::ONE && b != I256::ONE);
+}
+
+#[test]
+fn test_mpqs_too_few_relations() {
+    // with A kept near its ideal size there are only so many polynomials, which for
+    // this small factor base and interval give too few relations, rather than overflowing
+    let err = mpqs(1000003i64 * 1000033, 10, 200, DEFAULT_FUDGE).unwrap_err();
+    match err {
+        QsError::NotEnoughRelations { found, needed } => assert!(found < needed),
+        _ => panic!("unexpected error {}", err),
+    }
+}
+
+#[test]
+fn test_siqs() {
+    use quadratic_sieve::bigint::I256;
+
+    let n: I256 = "1000000016000000063".parse().unwrap(); // 1000000007 * 1000000009
+    let (a, b) = siqs(n, 200, 10000, DEFAULT_FUDGE).unwrap();
+    assert_eq!(a * b, n);
+    assert!(a != I256::ONE && b != I256::ONE);
+}
+
+#[test]
+fn test_siqs_large_primes() {
+    // a higher threshold fudge lets through more values with large primes,
+    // (including pairs of them) which are combined into relations
+    use quadratic_sieve::bigint::I256;
+
+    let n: I256 = "1000000016000000063".parse().unwrap(); // 1000000007 * 1000000009
+    let (res, stats) = siqs_with_stats(n, 100, 5000, 2.5);
+    let (a, b) = res.unwrap();
+    assert_eq!(a * b, n);
+    assert!(a != I256::ONE && b != I256::ONE);
+    assert!(stats.partial > 0 && stats.cycles > 0, "{}", stats);
+}
+
+#[test]
+fn test_select_multiplier() {
+    use quadratic_sieve::quadratic_sieve::select_multiplier;
+
+    let n: i64 = 1000000016000000063;
+    let k = select_multiplier(&n) as i64;
+    assert!((2..10).all(|p| k % (p * p) != 0));
+    // n is a square modulo only 8 of the odd primes below 100, and 3n modulo 17
+    let roots = |kn: i64| {
+        (3..100)
+            .filter(|&p| (2..p).all(|d| p % d != 0))
+            .filter(|&p| (0..p).any(|r| (r * r - kn).rem_euclid(p) == 0))
+            .count()
+    };
+    assert!(roots(k * n) > roots(n));
+}
+
+#[test]
+fn test_qs_params() {
+    let small = QsParams::for_n(&2021027i64);
+    let large = QsParams::for_n(&1000000016000000063i64);
+    assert!(small.b < large.b && small.m < large.m);
+    // beyond the last row of the table (80 digits), rather than extrapolating
+    use quadratic_sieve::bigint::I512;
+    let last = QsParams::for_n(&I512::from(10).pow(79));
+    let beyond = QsParams::for_n(&I512::from(10).pow(120));
+    assert_eq!(beyond, last);
+}
+
+#[test]
+fn test_qs_auto() {
+    use quadratic_sieve::bigint::I256;
+
+    let (a, b) = qs_auto(1009i64 * 2003).unwrap();
+    assert!(a == 1009 || a == 2003);
+    assert_eq!(a * b, 1009 * 2003);
+
+    let n: I256 = "21000000136000000019".parse().unwrap(); // 3000000019 * 7000000001
+    let (a, b) = qs_auto(n).unwrap();
+    assert_eq!(a * b, n);
+    assert!(a != I256::ONE && b != I256::ONE);
+}
+
+#[test]
+fn test_large_primes() {
+    use quadratic_sieve::bigint::I512;
+    use quadratic_sieve::quadratic_sieve::large_primes;
+
+    let bound = 1i64 << 21;
+    assert_eq!(large_primes(1009, &bound), Some([1, 1009]));
+    // cofactors above 31 bits, as the sieve gives with two large primes
+    for &(p, q) in [(15803, 136963), (48179, 57697), (1000003, 1000033)].iter() {
+        assert_eq!(large_primes(p * q, &bound), Some([p, q]));
+        let (p, q) = (I512::from(p as i32), I512::from(q as i32));
+        assert_eq!(large_primes(p * q, &I512::from(1 << 21)), Some([p, q]));
+    }
+    // one of the primes is above the bound, or c is a prime above it
+    assert_eq!(large_primes(1009 * 1000000007, &bound), None);
+    assert_eq!(large_primes(1000000007, &bound), None);
+}
@@ -0,0 +1,34 @@
+use quadratic_sieve::bigint::I256;
+use quadratic_sieve::rho::{brent, rho};
+
+#[test]
+fn test_brent() {
+    let n: i64 = 1000003 * 1000033;
+    let d = brent(&n, &1, 1 << 16).unwrap();
+    assert!(d == 1000003 || d == 1000033);
+    assert_eq!(brent(&(2 * 1009i64), &1, 100), Some(2));
+    // too few iterations for the cycle modulo either prime
+    assert_eq!(brent(&n, &1, 4), None);
+}
+
+#[test]
+fn test_rho() {
+    // the steps needed grow like the square root of the smallest prime, not of n
+    let n = 10007i64 * 1000000007;
+    assert_eq!(rho(&n, 1 << 8), Some(10007));
+    assert_eq!(brent(&n, &1, 1 << 6), Some(10007));
+    assert_eq!(brent(&n, &1, 1 << 3), None);
+    let n = 1000000007i64 * 1000000009;
+    assert_eq!(brent(&n, &1, 1 << 12), None);
+    assert_eq!(rho(&n, 1 << 17), Some(1000000009));
+    // the sequence cycles modulo p before it does modulo p^2
+    assert_eq!(rho(&(1000003i64 * 1000003), 1 << 12), Some(1000003));
+    assert_eq!(rho(&1000003i64, 1 << 10), None);
+}
+
+#[test]
+fn test_rho_bigint() {
+    // 1000000007 * 1000000000000000000000007
+    let n: I256 = "1000000007000000000000007000000049".parse().unwrap();
+    assert_eq!(rho(&n, 1 << 16), Some(I256::from(1000000007)));
+}
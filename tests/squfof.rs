@@ -0,0 +1,35 @@
+use quadratic_sieve::squfof::squfof;
+
+#[test]
+fn test_squfof() {
+    // cofactors of two large primes, of the sizes the sieve gives
+    for &(p, q) in [
+        (15803u64, 136963u64),
+        (48179, 57697),
+        (1000003, 1000033),
+        (4000037, 45000017),
+    ]
+    .iter()
+    {
+        let d = squfof(p * q).unwrap();
+        assert!(d == p || d == q, "{} {} {}", p, q, d);
+    }
+    assert_eq!(squfof(2 * 1009), Some(2));
+    assert_eq!(squfof(1009 * 1009), Some(1009));
+    assert_eq!(squfof(1000003), None);
+}
+
+#[test]
+fn test_squfof_large() {
+    // products of two primes near 2^30 and 2^31, as cofactors of the sieve are
+    for &(p, q) in [
+        (1073741827u64, 2147483659u64),
+        (1000000007, 1000000009),
+        (999999937, 2147483647),
+    ]
+    .iter()
+    {
+        let d = squfof(p * q).unwrap();
+        assert!(d == p || d == q, "{} {} {}", p, q, d);
+    }
+}